@@ -5,6 +5,7 @@ import multipart from "@fastify/multipart";
 import { PublicKey } from "@solana/web3.js";
 import {
   initSolanaClient,
+  buildPocReceiptTransaction,
   mintBlocks,
   getPlayerStats,
   getGlobalStats,
@@ -19,6 +20,8 @@ import { initVision, classifyImage, type VisionResult } from "./vision.js";
 const MATERIAL_TYPES = new Set(["plastic", "glass", "metal", "paper"]);
 const DEFAULT_TIME_WINDOW_MS = 10 * 60 * 1000;
 const MIN_CONFIDENCE = 0.7;
+// ProofOfCollectionReceipt::ZONE_ID_MAX_LEN
+const MAX_ZONE_ID_LEN = 32;
 
 const server = Fastify({
   logger: true,
@@ -71,12 +74,17 @@ server.get("/health", async () => ({
 }));
 
 // ── POST /verify ───────────────────────────────────────────────────────
-// Accepts multipart: image file + player_wallet field
+// Accepts multipart: image file + player_wallet, zone_id, lat and lon fields.
+// Returns a verifier-signed receipt transaction for the player to sign and
+// send; the receipt is redeemed for BLOCK through /redeem afterwards.
 server.post("/verify", async (request, reply) => {
   const parts = request.parts();
   let imageBuffer: Buffer | null = null;
   let imageMimeType = "image/jpeg";
   let playerWallet: string | null = null;
+  let zoneId: string | null = null;
+  let lat: number | null = null;
+  let lon: number | null = null;
 
   for await (const part of parts) {
     if (part.type === "file" && part.fieldname === "image") {
@@ -85,6 +93,12 @@ server.post("/verify", async (request, reply) => {
     } else if (part.type === "field") {
       if (part.fieldname === "player_wallet") {
         playerWallet = String(part.value).trim();
+      } else if (part.fieldname === "zone_id") {
+        zoneId = String(part.value).trim();
+      } else if (part.fieldname === "lat") {
+        lat = parseNumber(part.value);
+      } else if (part.fieldname === "lon") {
+        lon = parseNumber(part.value);
       }
     }
   }
@@ -102,6 +116,18 @@ server.post("/verify", async (request, reply) => {
     errors.push("player_wallet must be a valid Solana public key");
   }
 
+  if (!zoneId) {
+    errors.push("zone_id field is required");
+  } else if (Buffer.byteLength(zoneId, "utf-8") > MAX_ZONE_ID_LEN) {
+    errors.push(`zone_id must be at most ${MAX_ZONE_ID_LEN} bytes`);
+  }
+
+  if (lat === null || lon === null) {
+    errors.push("lat and lon fields must be numbers");
+  } else if (Math.abs(lat) > 90 || Math.abs(lon) > 180) {
+    errors.push("lat and lon must be valid coordinates");
+  }
+
   if (errors.length > 0) {
     reply.code(400);
     return { ok: false, errors };
@@ -138,28 +164,37 @@ server.post("/verify", async (request, reply) => {
     };
   }
 
-  // ── Step 3: Mint BLOCK tokens on-chain ───────────────────────────────
-  const blocksToMint = Math.max(
-    1,
-    Math.round(classification.estimated_weight_lbs)
-  );
-  const wasteTypeU8 = wasteTypeToU8(classification.waste_type);
+  // ── Step 3: Sign a proof-of-collection receipt ───────────────────────
+  // Quantity is in pounds; each material's units_per_block sets the BLOCK rate.
+  const player = new PublicKey(playerWallet!);
+  const photoHash = crypto.createHash("sha256").update(imageBuffer!).digest();
+  const receipt = {
+    player,
+    // One receipt per player and photo; the program also rejects reused photos
+    attestationId: crypto
+      .createHash("sha256")
+      .update(Buffer.concat([player.toBuffer(), photoHash]))
+      .digest(),
+    photoHash,
+    zoneId: zoneId!,
+    latE7: Math.round(lat! * 1e7),
+    lonE7: Math.round(lon! * 1e7),
+    materialType: wasteTypeToU8(classification.waste_type),
+    quantity: Math.max(1, Math.round(classification.estimated_weight_lbs)),
+    timestamp: Math.floor(Date.now() / 1000),
+  };
 
-  let txSignature: string;
+  let signed: Awaited<ReturnType<typeof buildPocReceiptTransaction>>;
   try {
-    txSignature = await mintBlocks(
-      new PublicKey(playerWallet!),
-      blocksToMint,
-      wasteTypeU8
-    );
+    signed = await buildPocReceiptTransaction(receipt);
   } catch (err: any) {
-    server.log.error({ err }, "Mint transaction failed");
+    server.log.error({ err }, "Receipt signing failed");
     reply.code(500);
     return {
       ok: false,
       verified: true,
       classification,
-      error: "On-chain mint failed",
+      error: "Receipt signing failed",
       detail: err.message,
       transaction: null,
     };
@@ -170,33 +205,85 @@ server.post("/verify", async (request, reply) => {
     ok: true,
     verified: true,
     classification,
-    blocksMinted: blocksToMint,
-    transaction: txSignature,
+    attestationId: receipt.attestationId.toString("hex"),
+    receipt: signed.receiptPda.toBase58(),
+    quantity: receipt.quantity,
+    // Unsigned, base64-encoded; the player signs and sends it
+    receiptTransaction: signed.transaction,
+    transaction: null,
     playerWallet,
   };
 });
 
+// ── POST /redeem ───────────────────────────────────────────────────────
+// Mints BLOCK for a submitted receipt once its challenge window has closed.
+server.post<{ Body: { player_wallet?: string; attestation_id?: string } }>(
+  "/redeem",
+  async (request, reply) => {
+    const playerWallet = request.body?.player_wallet?.trim() ?? "";
+    const attestationId = request.body?.attestation_id?.trim() ?? "";
+    const errors: string[] = [];
+
+    if (!isValidPubkey(playerWallet)) {
+      errors.push("player_wallet must be a valid Solana public key");
+    }
+    if (!/^[0-9a-f]{64}$/i.test(attestationId)) {
+      errors.push("attestation_id must be 32 bytes of hex");
+    }
+
+    if (errors.length > 0) {
+      reply.code(400);
+      return { ok: false, errors };
+    }
+
+    try {
+      const { transaction, blocksMinted } = await mintBlocks(
+        new PublicKey(playerWallet),
+        Buffer.from(attestationId, "hex")
+      );
+      reply.code(200);
+      return { ok: true, transaction, blocksMinted, playerWallet };
+    } catch (err: any) {
+      server.log.error({ err }, "Mint transaction failed");
+      reply.code(500);
+      return {
+        ok: false,
+        error: "On-chain mint failed",
+        detail: err.message,
+        transaction: null,
+      };
+    }
+  }
+);
+
 // ── POST /convert ──────────────────────────────────────────────────────
-// Burns 10 BLOCK → 1 Brick for the authority wallet
+// Burns `count` bricks' worth of BLOCK → `count` Brick for the authority wallet
 // In production, the player would sign from their own wallet.
 // For the hackathon demo, this uses the server's authority keypair.
-server.post<{ Body: { player_wallet?: string } }>(
+server.post<{ Body: { player_wallet?: string; count?: number } }>(
   "/convert",
   async (request, reply) => {
+    const count = parseNumber(request.body?.count) ?? 1;
+    if (!Number.isInteger(count) || count <= 0) {
+      reply.code(400);
+      return { ok: false, errors: ["count must be a positive integer"] };
+    }
+
     // For now, convert only works for the authority wallet
     // (because we need the player's private key to sign the burn)
     try {
       const { convertToBrickAsAuthority } = await import(
         "./solana-client.js"
       );
-      const tx = await convertToBrickAsAuthority();
+      const tx = await convertToBrickAsAuthority(count);
+      const stats = await getGlobalStats();
 
       reply.code(200);
       return {
         ok: true,
         transaction: tx,
-        blocksConverted: 10,
-        bricksReceived: 1,
+        blocksConverted: stats ? count * stats.blocksPerBrick : undefined,
+        bricksReceived: count,
       };
     } catch (err: any) {
       server.log.error({ err }, "Convert transaction failed");
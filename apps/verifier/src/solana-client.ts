@@ -3,6 +3,8 @@ import {
   PublicKey,
   Keypair,
   SystemProgram,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
   SYSVAR_RENT_PUBKEY,
 } from "@solana/web3.js";
 import {
@@ -13,6 +15,7 @@ import {
 } from "@solana/spl-token";
 import * as anchor from "@coral-xyz/anchor";
 import { BN } from "bn.js";
+import crypto from "node:crypto";
 import fs from "node:fs";
 import path from "node:path";
 
@@ -38,6 +41,44 @@ const [blockMintPda] = PublicKey.findProgramAddressSync(
   PROGRAM_ID
 );
 
+const [brickMintPda] = PublicKey.findProgramAddressSync(
+  [Buffer.from("brick_mint")],
+  PROGRAM_ID
+);
+
+const [verifierRegistryPda] = PublicKey.findProgramAddressSync(
+  [Buffer.from("verifier_registry")],
+  PROGRAM_ID
+);
+
+function playerProfilePdaFor(playerWallet: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("player"), playerWallet.toBuffer()],
+    PROGRAM_ID
+  )[0];
+}
+
+function receiptPdaFor(playerWallet: PublicKey, attestationId: Buffer): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("poc"), playerWallet.toBuffer(), attestationId],
+    PROGRAM_ID
+  )[0];
+}
+
+function materialPdaFor(materialType: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("material"), Buffer.from([materialType])],
+    PROGRAM_ID
+  )[0];
+}
+
+function zonePdaFor(zoneId: string): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("zone"), Buffer.from(zoneId, "utf-8")],
+    PROGRAM_ID
+  )[0];
+}
+
 // ── Waste type mapping ─────────────────────────────────────────────────
 const WASTE_TYPE_MAP: Record<string, number> = {
   plastic: 0,
@@ -120,19 +161,159 @@ export async function initSolanaClient() {
   console.log(`[solana] Mint:      ${blockMintPda.toBase58()}`);
 }
 
-// Player profile is auto-created via init_if_needed in the mint_blocks instruction.
-// No separate initialization step needed.
+// ── Proof-of-collection receipts ───────────────────────────────────────
+// A receipt is what this service vouches for after verifying a photo. The
+// player submits it on chain together with our Ed25519 signature, and once
+// its challenge window has passed we redeem it for BLOCK.
+export type PocReceipt = {
+  player: PublicKey;
+  attestationId: Buffer;
+  photoHash: Buffer;
+  zoneId: string;
+  latE7: number;
+  lonE7: number;
+  materialType: number;
+  quantity: number;
+  timestamp: number;
+};
+
+const POC_ATTESTATION_DOMAIN = Buffer.from("ecobuild:poc:v2");
+
+// Must match ProofOfCollectionReceipt::attestation_message in the program.
+export function pocAttestationMessage(receipt: PocReceipt): Buffer {
+  const zone = Buffer.from(receipt.zoneId, "utf-8");
+  const location = Buffer.alloc(8);
+  location.writeInt32LE(receipt.latE7, 0);
+  location.writeInt32LE(receipt.lonE7, 4);
+  const timestamp = Buffer.alloc(8);
+  timestamp.writeBigInt64LE(BigInt(receipt.timestamp));
+
+  return Buffer.concat([
+    POC_ATTESTATION_DOMAIN,
+    receipt.player.toBuffer(),
+    receipt.attestationId,
+    receipt.photoHash,
+    Buffer.from([zone.length]),
+    zone,
+    location,
+    Buffer.from([receipt.materialType]),
+    new BN(receipt.quantity).toArrayLike(Buffer, "le", 8),
+    timestamp,
+  ]);
+}
+
+// Verifiers sign the SHA-256 of the attestation message.
+export function pocAttestationHash(receipt: PocReceipt): Buffer {
+  return crypto
+    .createHash("sha256")
+    .update(pocAttestationMessage(receipt))
+    .digest();
+}
+
+// ── Build a signed receipt transaction for the player ──────────────────
+// mint_poc_receipt must be signed by the player, so we only sign the receipt
+// hash and hand back an unsigned transaction for the player's wallet.
+export async function buildPocReceiptTransaction(
+  receipt: PocReceipt
+): Promise<{ transaction: string; receiptPda: PublicKey }> {
+  const playerWallet = receipt.player;
+  const playerProfilePda = playerProfilePdaFor(playerWallet);
+  const receiptPda = receiptPdaFor(playerWallet, receipt.attestationId);
+  const [photoHashClaimPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("photo"), receipt.photoHash],
+    PROGRAM_ID
+  );
+
+  // Don't vouch for receipts the program would reject as worth no BLOCK
+  const material = await (program.account as any).materialConfig.fetch(
+    materialPdaFor(receipt.materialType)
+  );
+  if (!material.active) {
+    throw new Error(`Material ${receipt.materialType} is inactive`);
+  }
+  if (new BN(receipt.quantity).lt(material.unitsPerBlock)) {
+    throw new Error(
+      `Quantity ${receipt.quantity} is below ${material.unitsPerBlock.toString()} units per BLOCK`
+    );
+  }
+
+  const verifierIx = Ed25519Program.createInstructionWithPrivateKey({
+    privateKey: authorityKeypair.secretKey,
+    message: pocAttestationHash(receipt),
+  });
+  const preInstructions = [verifierIx];
+
+  // First-time players need a profile before they can hold receipts
+  const profileInfo = await provider.connection.getAccountInfo(playerProfilePda);
+  if (!profileInfo) {
+    preInstructions.push(
+      await (program.methods as any)
+        .initializePlayer()
+        .accounts({
+          authority: playerWallet,
+          playerProfile: playerProfilePda,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction()
+    );
+  }
+
+  const tx = await (program.methods as any)
+    .mintPocReceipt(
+      Array.from(receipt.attestationId),
+      Array.from(receipt.photoHash),
+      receipt.zoneId,
+      receipt.materialType,
+      new BN(receipt.quantity),
+      new BN(receipt.timestamp),
+      { latE7: receipt.latE7, lonE7: receipt.lonE7 }
+    )
+    .accounts({
+      authority: playerWallet,
+      playerProfile: playerProfilePda,
+      globalConfig: globalConfigPda,
+      materialConfig: materialPdaFor(receipt.materialType),
+      zone: zonePdaFor(receipt.zoneId),
+      pocReceipt: receiptPda,
+      photoHashClaim: photoHashClaimPda,
+      verifierRegistry: verifierRegistryPda,
+      instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      systemProgram: SystemProgram.programId,
+    })
+    .preInstructions(preInstructions)
+    .transaction();
+
+  const { blockhash } = await provider.connection.getLatestBlockhash();
+  tx.feePayer = playerWallet;
+  tx.recentBlockhash = blockhash;
+
+  return {
+    transaction: tx
+      .serialize({ requireAllSignatures: false, verifySignatures: false })
+      .toString("base64"),
+    receiptPda,
+  };
+}
 
 // ── Mint BLOCK tokens ──────────────────────────────────────────────────
+// Redeems a submitted receipt once its challenge window has closed. Only the
+// configured verifier may do this.
 export async function mintBlocks(
   playerWallet: PublicKey,
-  amount: number,
-  wasteType: number
-): Promise<string> {
-  const [playerProfilePda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("player"), playerWallet.toBuffer()],
-    PROGRAM_ID
+  attestationId: Buffer
+): Promise<{ transaction: string; blocksMinted: number }> {
+  const playerProfilePda = playerProfilePdaFor(playerWallet);
+  const receiptPda = receiptPdaFor(playerWallet, attestationId);
+  const receipt = await (program.account as any).proofOfCollectionReceipt.fetch(
+    receiptPda
+  );
+  const zoneId = Buffer.from(receipt.zoneId.slice(0, receipt.zoneIdLen)).toString(
+    "utf-8"
   );
+  const material = await (program.account as any).materialConfig.fetch(
+    materialPdaFor(receipt.materialType)
+  );
+  const blocksMinted = receipt.quantity.div(material.unitsPerBlock).toNumber();
 
   const playerAta = await getAssociatedTokenAddress(
     blockMintPda,
@@ -140,12 +321,15 @@ export async function mintBlocks(
   );
 
   const tx = await (program.methods as any)
-    .mintBlocks(new BN(amount), wasteType)
+    .mintBlocks()
     .accounts({
       authority: authorityKeypair.publicKey,
       globalConfig: globalConfigPda,
       blockMint: blockMintPda,
       playerProfile: playerProfilePda,
+      pocReceipt: receiptPda,
+      materialConfig: materialPdaFor(receipt.materialType),
+      zone: zonePdaFor(zoneId),
       playerTokenAccount: playerAta,
       playerAuthority: playerWallet,
       tokenProgram: TOKEN_PROGRAM_ID,
@@ -155,14 +339,15 @@ export async function mintBlocks(
     .rpc();
 
   console.log(
-    `[solana] Minted ${amount} BLOCK to ${playerWallet.toBase58()} — tx: ${tx}`
+    `[solana] Redeemed receipt ${receiptPda.toBase58()} for ${blocksMinted} BLOCK to ${playerWallet.toBase58()} — tx: ${tx}`
   );
-  return tx;
+  return { transaction: tx, blocksMinted };
 }
 
 // ── Convert BLOCK → Brick ──────────────────────────────────────────────
 export async function convertToBrick(
-  playerKeypair: Keypair
+  playerKeypair: Keypair,
+  count: number
 ): Promise<string> {
   const playerWallet = playerKeypair.publicKey;
 
@@ -175,6 +360,10 @@ export async function convertToBrick(
     blockMintPda,
     playerWallet
   );
+  const playerBrickAta = await getAssociatedTokenAddress(
+    brickMintPda,
+    playerWallet
+  );
 
   // For convert, the PLAYER is the signer (they burn their own tokens).
   // We need a provider with the player's wallet.
@@ -187,25 +376,29 @@ export async function convertToBrick(
   const playerProgram = new anchor.Program(idl, playerProvider);
 
   const tx = await (playerProgram.methods as any)
-    .convertToBrick()
+    .convertToBrick(new BN(count))
     .accounts({
       authority: playerWallet,
       globalConfig: globalConfigPda,
       blockMint: blockMintPda,
+      brickMint: brickMintPda,
       playerProfile: playerProfilePda,
       playerTokenAccount: playerAta,
+      playerBrickAccount: playerBrickAta,
       tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
     })
     .rpc();
 
-  console.log(`[solana] Converted 10 BLOCK → 1 Brick for ${playerWallet.toBase58()} — tx: ${tx}`);
+  console.log(`[solana] Converted BLOCK → ${count} Brick for ${playerWallet.toBase58()} — tx: ${tx}`);
   return tx;
 }
 
 // ── Convert using authority (for API endpoint where player isn't signing) ──
-export async function convertToBrickAsAuthority(): Promise<string> {
+export async function convertToBrickAsAuthority(count: number): Promise<string> {
   // When called via API, we use the authority keypair as the player too
-  return convertToBrick(authorityKeypair);
+  return convertToBrick(authorityKeypair, count);
 }
 
 // ── Get player stats ───────────────────────────────────────────────────
@@ -235,7 +428,7 @@ export async function getPlayerStats(playerWallet: PublicKey) {
 
     return {
       wallet: playerWallet.toBase58(),
-      totalCredits: profile.totalCredits.toNumber(),
+      creditsContributed: profile.creditsContributed.toNumber(),
       blocksMinted: profile.blocksMinted.toNumber(),
       brickCount: profile.brickCount.toNumber(),
       collectionsCount: profile.collectionsCount.toNumber(),
@@ -255,6 +448,7 @@ export async function getGlobalStats() {
     return {
       authority: config.authority.toBase58(),
       blockMint: config.blockMint.toBase58(),
+      blocksPerBrick: config.blocksPerBrick.toNumber(),
       totalBlocksMinted: config.totalBlocksMinted.toNumber(),
       totalBricksCreated: config.totalBricksCreated.toNumber(),
     };
@@ -264,13 +458,15 @@ export async function getGlobalStats() {
 }
 
 // ── Initialize config (one-time setup) ─────────────────────────────────
+// This service's keypair becomes the verifier that signs receipts and mints.
 export async function initializeConfig(): Promise<string> {
   const tx = await (program.methods as any)
-    .initializeConfig()
+    .initializeConfig(authorityKeypair.publicKey)
     .accounts({
       authority: authorityKeypair.publicKey,
       globalConfig: globalConfigPda,
       blockMint: blockMintPda,
+      brickMint: brickMintPda,
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
       rent: SYSVAR_RENT_PUBKEY,
@@ -281,4 +477,10 @@ export async function initializeConfig(): Promise<string> {
   return tx;
 }
 
-export { authorityKeypair, globalConfigPda, blockMintPda, PROGRAM_ID };
+export {
+  authorityKeypair,
+  globalConfigPda,
+  blockMintPda,
+  brickMintPda,
+  PROGRAM_ID,
+};
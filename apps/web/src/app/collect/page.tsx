@@ -2,8 +2,15 @@
 
 import { useState, useRef, useCallback } from "react";
 import dynamic from "next/dynamic";
-import { useWallet } from "@solana/wallet-adapter-react";
-import { verifyWaste, type VerifyResult } from "@/lib/api";
+import { useConnection, useWallet } from "@solana/wallet-adapter-react";
+import { Transaction } from "@solana/web3.js";
+import {
+  redeemReceipt,
+  verifyWaste,
+  type CollectLocation,
+  type RedeemResult,
+  type VerifyResult,
+} from "@/lib/api";
 
 const WalletMultiButton = dynamic(
   () =>
@@ -13,7 +20,7 @@ const WalletMultiButton = dynamic(
   { ssr: false }
 );
 import { useBlockBalance } from "@/lib/hooks";
-import { explorerTxUrl } from "@/lib/constants";
+import { ZONE_ID, explorerAddrUrl, explorerTxUrl } from "@/lib/constants";
 import {
   Camera,
   Upload,
@@ -22,18 +29,55 @@ import {
   XCircle,
   ExternalLink,
   ImagePlus,
+  Clock,
 } from "lucide-react";
 
-type Stage = "idle" | "uploading" | "success" | "error";
+// pending: receipt is on chain but its challenge window has not closed yet
+type Stage =
+  | "idle"
+  | "uploading"
+  | "signing"
+  | "redeeming"
+  | "pending"
+  | "success"
+  | "error";
+
+function currentLocation(): Promise<CollectLocation> {
+  return new Promise((resolve, reject) => {
+    if (!navigator.geolocation) {
+      reject(new Error("Location is unavailable in this browser"));
+      return;
+    }
+    navigator.geolocation.getCurrentPosition(
+      (pos) =>
+        resolve({
+          zoneId: ZONE_ID,
+          lat: pos.coords.latitude,
+          lon: pos.coords.longitude,
+        }),
+      () => reject(new Error("Allow location access to verify a collection")),
+      { enableHighAccuracy: true, timeout: 15_000 }
+    );
+  });
+}
+
+function decodeTransaction(base64: string): Transaction {
+  return Transaction.from(
+    Uint8Array.from(atob(base64), (c) => c.charCodeAt(0))
+  );
+}
 
 export default function CollectPage() {
-  const { publicKey, connected } = useWallet();
+  const { publicKey, connected, sendTransaction } = useWallet();
+  const { connection } = useConnection();
   const { refresh: refreshBalance } = useBlockBalance();
 
   const [file, setFile] = useState<File | null>(null);
   const [preview, setPreview] = useState<string | null>(null);
   const [stage, setStage] = useState<Stage>("idle");
   const [result, setResult] = useState<VerifyResult | null>(null);
+  const [receiptTx, setReceiptTx] = useState<string | null>(null);
+  const [redeemed, setRedeemed] = useState<RedeemResult | null>(null);
   const [errorMsg, setErrorMsg] = useState("");
   const inputRef = useRef<HTMLInputElement>(null);
 
@@ -42,6 +86,8 @@ export default function CollectPage() {
     setPreview(URL.createObjectURL(f));
     setStage("idle");
     setResult(null);
+    setReceiptTx(null);
+    setRedeemed(null);
     setErrorMsg("");
   }, []);
 
@@ -54,31 +100,67 @@ export default function CollectPage() {
     [handleFile]
   );
 
+  // BLOCKs can only be minted once the receipt's challenge window closes;
+  // until then the receipt stays pending and can be claimed later.
+  const claim = async (attestationId: string) => {
+    if (!publicKey) return;
+    setStage("redeeming");
+    try {
+      const data = await redeemReceipt(publicKey.toBase58(), attestationId);
+      if (data.ok) {
+        setRedeemed(data);
+        setStage("success");
+        refreshBalance();
+      } else {
+        setStage("pending");
+      }
+    } catch {
+      setStage("pending");
+    }
+  };
+
   const submit = async () => {
     if (!file || !publicKey) return;
     setStage("uploading");
     setErrorMsg("");
     try {
-      const data = await verifyWaste(file, publicKey.toBase58());
+      const location = await currentLocation();
+      const data = await verifyWaste(file, publicKey.toBase58(), location);
       setResult(data);
-      if (data.ok && data.verified) {
-        setStage("success");
-        refreshBalance();
-      } else {
+      if (!data.ok || !data.verified || !data.receiptTransaction) {
         setStage("error");
-        setErrorMsg(data.reason || data.error || "Verification failed");
+        setErrorMsg(
+          data.reason ||
+            data.errors?.join(", ") ||
+            data.error ||
+            "Verification failed"
+        );
+        return;
       }
+
+      setStage("signing");
+      const tx = decodeTransaction(data.receiptTransaction);
+      const sig = await sendTransaction(tx, connection);
+      await connection.confirmTransaction(sig, "confirmed");
+      setReceiptTx(sig);
+
+      await claim(data.attestationId!);
     } catch (err: unknown) {
       setStage("error");
       setErrorMsg(err instanceof Error ? err.message : "Network error — is the verifier running?");
     }
   };
 
+  const busy =
+    stage === "uploading" || stage === "signing" || stage === "redeeming";
+
   const reset = () => {
     setFile(null);
     setPreview(null);
     setStage("idle");
     setResult(null);
+    setReceiptTx(null);
+    setRedeemed(null);
     setErrorMsg("");
   };
 
@@ -119,7 +201,7 @@ export default function CollectPage() {
           preview
             ? "border-eco-300 bg-eco-50"
             : "border-stone-300 bg-stone-50 hover:border-eco-400 hover:bg-eco-50"
-        } ${stage === "uploading" ? "pointer-events-none opacity-60" : ""}`}
+        } ${busy ? "pointer-events-none opacity-60" : ""}`}
         style={{ minHeight: 280 }}
       >
         <input
@@ -161,14 +243,18 @@ export default function CollectPage() {
           </button>
         )}
         <button
-          disabled={!file || stage === "uploading"}
+          disabled={!file || busy || stage === "pending" || stage === "success"}
           onClick={submit}
           className="flex flex-1 items-center justify-center gap-2 rounded-lg bg-eco-600 px-4 py-2.5 text-sm font-semibold text-white transition-colors hover:bg-eco-700 disabled:cursor-not-allowed disabled:opacity-40"
         >
-          {stage === "uploading" ? (
+          {busy ? (
             <>
               <Loader2 className="h-4 w-4 animate-spin" />
-              Verifying...
+              {stage === "uploading"
+                ? "Verifying..."
+                : stage === "signing"
+                  ? "Approve in wallet..."
+                  : "Claiming BLOCKs..."}
             </>
           ) : (
             <>
@@ -180,7 +266,7 @@ export default function CollectPage() {
       </div>
 
       {/* Result card */}
-      {stage === "success" && result && (
+      {(stage === "success" || stage === "pending") && result && (
         <div className="space-y-3 rounded-xl border border-eco-200 bg-eco-50 p-5">
           <div className="flex items-center gap-2 text-eco-700">
             <CheckCircle2 className="h-5 w-5" />
@@ -201,22 +287,65 @@ export default function CollectPage() {
                 (result.classification?.confidence ?? 0) * 100
               )}%`}
             />
-            <Kv label="BLOCKs Earned" value={result.blocksMinted ?? 0} />
+            <Kv label="Quantity" value={result.quantity ?? 0} />
+            <Kv
+              label="BLOCKs Earned"
+              value={redeemed?.blocksMinted ?? "Pending"}
+            />
           </div>
           {result.classification?.description && (
             <p className="text-sm text-stone-600">
               {result.classification.description}
             </p>
           )}
-          {result.transaction && (
-            <a
-              href={explorerTxUrl(result.transaction)}
-              target="_blank"
-              rel="noopener noreferrer"
-              className="inline-flex items-center gap-1 text-sm font-medium text-eco-600 hover:underline"
+          {stage === "pending" && (
+            <div className="flex items-start gap-2 rounded-lg bg-white p-3 text-sm text-stone-600">
+              <Clock className="mt-0.5 h-4 w-4 flex-shrink-0 text-stone-400" />
+              <span>
+                Your receipt is on chain. BLOCKs can be claimed once its
+                challenge window closes.
+              </span>
+            </div>
+          )}
+          <div className="flex flex-wrap gap-4">
+            {result.receipt && (
+              <a
+                href={explorerAddrUrl(result.receipt)}
+                target="_blank"
+                rel="noopener noreferrer"
+                className="inline-flex items-center gap-1 text-sm font-medium text-eco-600 hover:underline"
+              >
+                View receipt <ExternalLink className="h-3.5 w-3.5" />
+              </a>
+            )}
+            {receiptTx && (
+              <a
+                href={explorerTxUrl(receiptTx)}
+                target="_blank"
+                rel="noopener noreferrer"
+                className="inline-flex items-center gap-1 text-sm font-medium text-eco-600 hover:underline"
+              >
+                Receipt transaction <ExternalLink className="h-3.5 w-3.5" />
+              </a>
+            )}
+            {redeemed?.transaction && (
+              <a
+                href={explorerTxUrl(redeemed.transaction)}
+                target="_blank"
+                rel="noopener noreferrer"
+                className="inline-flex items-center gap-1 text-sm font-medium text-eco-600 hover:underline"
+              >
+                Mint transaction <ExternalLink className="h-3.5 w-3.5" />
+              </a>
+            )}
+          </div>
+          {stage === "pending" && result.attestationId && (
+            <button
+              onClick={() => claim(result.attestationId!)}
+              className="w-full rounded-lg border border-eco-300 px-4 py-2.5 text-sm font-semibold text-eco-700 hover:bg-eco-100"
             >
-              View transaction <ExternalLink className="h-3.5 w-3.5" />
-            </a>
+              Claim BLOCKs
+            </button>
           )}
           <button
             onClick={reset}
@@ -10,7 +10,11 @@ export type VerifyResult = {
     confidence: number;
     description: string;
   };
-  blocksMinted?: number;
+  attestationId?: string;
+  receipt?: string;
+  quantity?: number;
+  // Unsigned, base64-encoded; signed and sent by the player's wallet
+  receiptTransaction?: string;
   transaction?: string | null;
   playerWallet?: string;
   reason?: string;
@@ -19,6 +23,22 @@ export type VerifyResult = {
   errors?: string[];
 };
 
+export type RedeemResult = {
+  ok: boolean;
+  transaction?: string;
+  blocksMinted?: number;
+  playerWallet?: string;
+  error?: string;
+  detail?: string;
+  errors?: string[];
+};
+
+export type CollectLocation = {
+  zoneId: string;
+  lat: number;
+  lon: number;
+};
+
 export type ConvertResult = {
   ok: boolean;
   transaction?: string;
@@ -31,7 +51,7 @@ export type ConvertResult = {
 export type PlayerStats = {
   ok: boolean;
   wallet?: string;
-  totalCredits?: number;
+  creditsContributed?: number;
   blocksMinted?: number;
   brickCount?: number;
   collectionsCount?: number;
@@ -41,11 +61,15 @@ export type PlayerStats = {
 
 export async function verifyWaste(
   image: File,
-  playerWallet: string
+  playerWallet: string,
+  location: CollectLocation
 ): Promise<VerifyResult> {
   const form = new FormData();
   form.append("image", image);
   form.append("player_wallet", playerWallet);
+  form.append("zone_id", location.zoneId);
+  form.append("lat", String(location.lat));
+  form.append("lon", String(location.lon));
 
   const res = await fetch(`${VERIFIER_URL}/verify`, {
     method: "POST",
@@ -55,6 +79,21 @@ export async function verifyWaste(
   return res.json();
 }
 
+export async function redeemReceipt(
+  playerWallet: string,
+  attestationId: string
+): Promise<RedeemResult> {
+  const res = await fetch(`${VERIFIER_URL}/redeem`, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({
+      player_wallet: playerWallet,
+      attestation_id: attestationId,
+    }),
+  });
+  return res.json();
+}
+
 export async function convertToBrick(): Promise<ConvertResult> {
   const res = await fetch(`${VERIFIER_URL}/convert`, {
     method: "POST",
@@ -70,7 +109,7 @@ export async function fetchPlayerStats(wallet: string): Promise<PlayerStats> {
   if (!res.ok && res.status === 404) {
     return {
       ok: true,
-      totalCredits: 0,
+      creditsContributed: 0,
       blocksMinted: 0,
       brickCount: 0,
       collectionsCount: 0,
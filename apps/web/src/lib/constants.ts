@@ -14,6 +14,9 @@ export const RPC_URL =
 export const VERIFIER_URL =
   process.env.NEXT_PUBLIC_VERIFIER_URL || "http://localhost:3000";
 
+// Collection zone submitted with each photo; must be registered on chain.
+export const ZONE_ID = process.env.NEXT_PUBLIC_ZONE_ID || "local";
+
 export const BLOCKS_PER_BRICK = 10;
 
 export const EXPLORER_URL = "https://explorer.solana.com";
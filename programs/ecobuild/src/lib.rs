@@ -1,14 +1,40 @@
 #![allow(unexpected_cfgs)]
 
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    hash::hash,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Burn, Mint, MintTo, Token, TokenAccount},
+    token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer},
+    token_2022::{spl_token_2022::instruction::AuthorityType, Token2022},
+    token_interface::{
+        self, spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::state::{Field, TokenMetadata},
+        token_metadata_initialize, token_metadata_update_field, TokenMetadataInitialize,
+        TokenMetadataUpdateField,
+    },
 };
 
 declare_id!("HcENn31gno9LMse5iERziSpLGjMdtLZAxLQo9Ff4xn5b");
 
-pub const BLOCKS_PER_BRICK: u64 = 10;
+/// Conversion rate set by `initialize_config`; adjustable via `update_config`.
+pub const DEFAULT_BLOCKS_PER_BRICK: u64 = 10;
+
+/// Seconds a new receipt stays open to disputes; adjustable via `update_config`.
+pub const DEFAULT_CHALLENGE_PERIOD: i64 = 24 * 60 * 60;
+
+/// Lamports staked by a receipt submitter and by each challenger (0.01 SOL).
+pub const DEFAULT_DISPUTE_BOND: u64 = 10_000_000;
+
+/// Oldest collection a new receipt may describe, in seconds (7 days).
+pub const DEFAULT_MAX_RECEIPT_AGE: i64 = 7 * 24 * 60 * 60;
+
+/// How far ahead of the cluster clock a receipt timestamp may be, in seconds.
+pub const DEFAULT_MAX_FUTURE_SKEW: i64 = 5 * 60;
 
 #[program]
 pub mod ecobuild {
@@ -22,813 +48,5331 @@ pub mod ecobuild {
 
     pub fn create_project_pool(
         ctx: Context<CreateProjectPool>,
-        project_seed: u64,
         name: String,
-        goal_credits: u64,
+        material_targets: Vec<MaterialTarget>,
+        deadline: i64,
     ) -> Result<()> {
-        ctx.accounts.project_pool.initialize(
+        let timestamp = Clock::get()?.unix_timestamp;
+        if deadline <= timestamp {
+            return Err(ErrorCode::InvalidTimestamp.into());
+        }
+
+        let id = ctx.accounts.global_config.next_project_id()?;
+        let pool = &mut ctx.accounts.project_pool;
+        pool.initialize(
             ctx.accounts.authority.key(),
             ctx.bumps.project_pool,
-            project_seed,
-            goal_credits,
+            id,
+            &material_targets,
             &name,
-        )
+            deadline,
+        )?;
+
+        emit!(ProjectCreated {
+            project_pool: pool.key(),
+            id,
+            authority: pool.authority,
+            goal_credits: pool.goal_credits,
+            deadline,
+            timestamp,
+        });
+
+        Ok(())
     }
 
-    pub fn contribute_credits(ctx: Context<ContributeCredits>, amount: u64) -> Result<()> {
+    pub fn init_material_vault(ctx: Context<InitMaterialVault>, material_id: u8) -> Result<()> {
+        ctx.accounts.material_config.require_usable(material_id)?;
+        let mint = ctx.accounts.vault_mint.key();
+        let pool = &mut ctx.accounts.project_pool;
+        pool.bind_material_vault(material_id, mint, ctx.bumps.vault)?;
+
+        emit!(MaterialVaultCreated {
+            project_pool: pool.key(),
+            material_id,
+            mint,
+            vault: ctx.accounts.vault.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn contribute_credits(
+        ctx: Context<ContributeCredits>,
+        amount: u64,
+        material_id: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .global_config
+            .require_not_paused(GlobalConfig::PAUSE_CONTRIBUTE)?;
+        ctx.accounts.material_config.require_usable(material_id)?;
         if amount == 0 {
             return Err(ErrorCode::InvalidAmount.into());
         }
+        if ctx.accounts.project_pool.status != ProjectStatus::Funding {
+            return Err(ErrorCode::ProjectNotFunding.into());
+        }
+        let timestamp = Clock::get()?.unix_timestamp;
+        if ctx.accounts.project_pool.is_expired(timestamp) {
+            return Err(ErrorCode::ProjectExpired.into());
+        }
+        if ctx.accounts.player_token_account.amount < amount {
+            return Err(ErrorCode::InsufficientBalance.into());
+        }
+
+        // Move the contribution into the material's vault
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.player_token_account.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
 
+        // Material progress follows its vault balance
+        ctx.accounts.vault.reload()?;
+        let vault_balance = ctx.accounts.vault.amount;
+        let mint = ctx.accounts.vault.mint;
         let player = &mut ctx.accounts.player_profile;
         let pool = &mut ctx.accounts.project_pool;
-        player.add_credits(amount)?;
-        pool.record_contribution(amount)
-    }
+        player.record_contribution(amount)?;
+        let slot = pool.sync_material_vault(material_id, mint, vault_balance)?;
 
-    pub fn mint_poc_receipt(
-        ctx: Context<MintProofOfCollectionReceipt>,
-        attestation_id: [u8; 32],
-        photo_hash: [u8; 32],
-        zone_id: String,
-        material_type: u8,
-        quantity: u64,
-        timestamp: i64,
-    ) -> Result<()> {
-        let player = &ctx.accounts.player_profile;
-        ctx.accounts.poc_receipt.initialize(
-            player.authority,
-            ctx.bumps.poc_receipt,
-            attestation_id,
-            photo_hash,
-            &zone_id,
-            material_type,
-            quantity,
+        // Track the contributor's share per material for refunds
+        let contribution = &mut ctx.accounts.contribution;
+        if contribution.contributor == Pubkey::default() {
+            contribution.initialize(pool.key(), player.authority, ctx.bumps.contribution);
+        }
+        contribution.record(slot, amount, timestamp)?;
+
+        emit!(CreditsContributed {
+            player: player.authority,
+            project_pool: pool.key(),
+            mint,
+            material_id,
+            amount,
+            vault_balance,
+            contributor_total: contribution.amount,
+            contribution_count: contribution.count,
             timestamp,
-        )
-    }
+        });
+
+        // Meeting every material target closes the pool to further contributions
+        if pool.goals_met() {
+            let from = pool.transition(ProjectStatus::Funded)?;
+            emit!(ProjectStatusChanged {
+                project_pool: pool.key(),
+                from,
+                to: pool.status,
+                timestamp,
+            });
+        }
 
-    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
-        let config = &mut ctx.accounts.global_config;
-        config.authority = ctx.accounts.authority.key();
-        config.block_mint = ctx.accounts.block_mint.key();
-        config.total_blocks_minted = 0;
-        config.total_bricks_created = 0;
-        config.bump = ctx.bumps.global_config;
         Ok(())
     }
 
-    pub fn mint_blocks(
-        ctx: Context<MintBlocks>,
-        amount: u64,
-        waste_type: u8,
-    ) -> Result<()> {
-        if amount == 0 {
-            return Err(ErrorCode::InvalidAmount.into());
+    pub fn claim_refund(ctx: Context<ClaimRefund>, material_id: u8) -> Result<()> {
+        let pool = &ctx.accounts.project_pool;
+        if !pool.is_refundable(Clock::get()?.unix_timestamp) {
+            return Err(ErrorCode::ProjectNotRefundable.into());
         }
-        MaterialType::try_from(waste_type)?;
+        let slot = pool.goal_index(material_id)?;
+        let amount = ctx.accounts.contribution.refund(slot)?;
 
-        // Mint BLOCK tokens to player's ATA using GlobalConfig PDA as mint authority
-        let seeds = &[
-            GlobalConfig::SEED_PREFIX,
-            &[ctx.accounts.global_config.bump],
-        ];
+        // Return the contributor's share from the material's vault, signed by the pool PDA
+        let id_bytes = pool.id.to_le_bytes();
+        let seeds = &[ProjectPool::SEED_PREFIX, &id_bytes, &[pool.bump]];
         let signer_seeds = &[&seeds[..]];
 
-        token::mint_to(
+        token::transfer(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                MintTo {
-                    mint: ctx.accounts.block_mint.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
                     to: ctx.accounts.player_token_account.to_account_info(),
-                    authority: ctx.accounts.global_config.to_account_info(),
+                    authority: ctx.accounts.project_pool.to_account_info(),
                 },
                 signer_seeds,
             ),
             amount,
         )?;
 
-        // Initialize player profile if newly created via init_if_needed
-        let player = &mut ctx.accounts.player_profile;
-        if player.authority == Pubkey::default() {
-            player.authority = ctx.accounts.player_authority.key();
-            player.bump = ctx.bumps.player_profile;
-            player.total_credits = 0;
-            player.blocks_minted = 0;
-            player.brick_count = 0;
-            player.collections_count = 0;
-        }
+        ctx.accounts.vault.reload()?;
+        let vault_balance = ctx.accounts.vault.amount;
+        let mint = ctx.accounts.vault.mint;
+        let pool = &mut ctx.accounts.project_pool;
+        pool.sync_material_vault(material_id, mint, vault_balance)?;
 
-        // Update player stats
-        player.blocks_minted = player
-            .blocks_minted
-            .checked_add(amount)
-            .ok_or(ErrorCode::Overflow)?;
-        player.collections_count = player
-            .collections_count
-            .checked_add(1)
-            .ok_or(ErrorCode::Overflow)?;
+        emit!(RefundClaimed {
+            player: ctx.accounts.authority.key(),
+            project_pool: pool.key(),
+            material_id,
+            amount,
+            vault_balance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Update global stats
-        let config = &mut ctx.accounts.global_config;
-        config.total_blocks_minted = config
-            .total_blocks_minted
-            .checked_add(amount)
-            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
 
-        emit!(BlocksMinted {
-            player: ctx.accounts.player_profile.authority,
-            amount,
-            waste_type,
+    pub fn open_project(ctx: Context<ManageProject>) -> Result<()> {
+        ctx.accounts.set_status(ProjectStatus::Funding)
+    }
+
+    pub fn complete_project(ctx: Context<ManageProject>) -> Result<()> {
+        ctx.accounts.set_status(ProjectStatus::Completed)
+    }
+
+    pub fn cancel_project(ctx: Context<ManageProject>) -> Result<()> {
+        ctx.accounts.set_status(ProjectStatus::Cancelled)
+    }
+
+    pub fn archive_project(ctx: Context<ManageProject>) -> Result<()> {
+        ctx.accounts.set_status(ProjectStatus::Archived)
+    }
+
+    pub fn set_project_milestones(
+        ctx: Context<ManageProject>,
+        builder: Pubkey,
+        milestones: Vec<Milestone>,
+    ) -> Result<()> {
+        let pool = &mut ctx.accounts.project_pool;
+        pool.set_milestones(builder, &milestones)?;
+
+        emit!(ProjectMilestonesSet {
+            project_pool: pool.key(),
+            builder,
+            milestones,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    pub fn convert_to_brick(ctx: Context<ConvertToBrick>) -> Result<()> {
-        // Check balance
-        if ctx.accounts.player_token_account.amount < BLOCKS_PER_BRICK {
-            return Err(ErrorCode::InsufficientBlocks.into());
-        }
+    pub fn propose_project_authority(
+        ctx: Context<ManageProject>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let pool = &mut ctx.accounts.project_pool;
+        pool.propose_authority(new_authority)?;
 
-        // Burn 10 BLOCK tokens from player's ATA
-        token::burn(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    mint: ctx.accounts.block_mint.to_account_info(),
-                    from: ctx.accounts.player_token_account.to_account_info(),
-                    authority: ctx.accounts.authority.to_account_info(),
-                },
-            ),
-            BLOCKS_PER_BRICK,
-        )?;
+        emit!(ProjectAuthorityProposed {
+            project_pool: pool.key(),
+            authority: pool.authority,
+            pending_authority: new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Update player stats
-        let player = &mut ctx.accounts.player_profile;
-        player.brick_count = player
-            .brick_count
-            .checked_add(1)
-            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
 
-        // Update global stats
-        let config = &mut ctx.accounts.global_config;
-        config.total_bricks_created = config
-            .total_bricks_created
-            .checked_add(1)
-            .ok_or(ErrorCode::Overflow)?;
+    pub fn accept_project_authority(ctx: Context<AcceptProjectAuthority>) -> Result<()> {
+        let pool = &mut ctx.accounts.project_pool;
+        let previous_authority = pool.accept_authority(ctx.accounts.new_authority.key())?;
 
-        emit!(BrickConverted {
-            player: player.authority,
-            new_brick_count: player.brick_count,
+        emit!(ProjectAuthorityTransferred {
+            project_pool: pool.key(),
+            previous_authority,
+            new_authority: pool.authority,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
-}
 
-// ── Account contexts ──────────────────────────────────────────────────
+    pub fn update_project_metadata(
+        ctx: Context<UpdateProjectMetadata>,
+        params: ProjectMetadataParams,
+    ) -> Result<()> {
+        let pool = &ctx.accounts.project_pool;
+        if !pool.is_open() {
+            return Err(ErrorCode::ProjectMetadataLocked.into());
+        }
 
-#[derive(Accounts)]
-pub struct InitializePlayer<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    #[account(
-        init,
-        payer = authority,
-        space = PlayerProfile::SIZE,
-        seeds = [PlayerProfile::SEED_PREFIX, authority.key().as_ref()],
-        bump
-    )]
-    pub player_profile: Account<'info, PlayerProfile>,
-    pub system_program: Program<'info, System>,
-}
+        let metadata = &mut ctx.accounts.project_metadata;
+        if metadata.project_pool == Pubkey::default() {
+            metadata.project_pool = pool.key();
+            metadata.bump = ctx.bumps.project_metadata;
+        }
+        let now = Clock::get()?.unix_timestamp;
+        metadata.update(&params, now)?;
 
-#[derive(Accounts)]
-#[instruction(project_seed: u64)]
-pub struct CreateProjectPool<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    #[account(
-        init,
-        payer = authority,
-        space = ProjectPool::SIZE,
-        seeds = [
-            ProjectPool::SEED_PREFIX,
-            authority.key().as_ref(),
-            &project_seed.to_le_bytes()
-        ],
-        bump
-    )]
-    pub project_pool: Account<'info, ProjectPool>,
-    pub system_program: Program<'info, System>,
-}
+        emit!(ProjectMetadataUpdated {
+            project_pool: metadata.project_pool,
+            authority: ctx.accounts.authority.key(),
+            uri: params.uri,
+            content_hash: params.content_hash,
+            image_uri: params.image_uri,
+            category: params.category,
+            zone_id: params.zone_id,
+            version: metadata.version,
+            timestamp: now,
+        });
 
-#[derive(Accounts)]
-pub struct ContributeCredits<'info> {
-    pub authority: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [PlayerProfile::SEED_PREFIX, authority.key().as_ref()],
-        bump = player_profile.bump,
-        constraint = player_profile.authority == authority.key()
-    )]
-    pub player_profile: Account<'info, PlayerProfile>,
-    #[account(
-        mut,
-        seeds = [
-            ProjectPool::SEED_PREFIX,
-            project_pool.authority.as_ref(),
-            &project_pool.seed.to_le_bytes()
-        ],
-        bump = project_pool.bump
-    )]
-    pub project_pool: Account<'info, ProjectPool>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-#[instruction(attestation_id: [u8; 32])]
-pub struct MintProofOfCollectionReceipt<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    #[account(
-        seeds = [PlayerProfile::SEED_PREFIX, authority.key().as_ref()],
-        bump = player_profile.bump,
-        constraint = player_profile.authority == authority.key()
-    )]
-    pub player_profile: Account<'info, PlayerProfile>,
-    #[account(
-        init,
-        payer = authority,
-        space = ProofOfCollectionReceipt::SIZE,
-        seeds = [
-            ProofOfCollectionReceipt::SEED_PREFIX,
-            player_profile.authority.as_ref(),
-            &attestation_id
-        ],
-        bump
-    )]
-    pub poc_receipt: Account<'info, ProofOfCollectionReceipt>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn submit_construction_receipt(
+        ctx: Context<SubmitConstructionReceipt>,
+        milestone_index: u8,
+        photo_hash: [u8; 32],
+        zone_id: String,
+        timestamp: i64,
+    ) -> Result<()> {
+        // Receipts are only taken against the locked plan of a funded pool
+        let pool = &ctx.accounts.project_pool;
+        if pool.status != ProjectStatus::Funded {
+            return Err(ErrorCode::ProjectNotFunded.into());
+        }
+        let description_hash = pool
+            .milestones()
+            .get(milestone_index as usize)
+            .ok_or(ErrorCode::NoMilestoneToRelease)?
+            .description_hash;
 
-#[derive(Accounts)]
-pub struct InitializeConfig<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    #[account(
-        init,
-        payer = authority,
-        space = GlobalConfig::SIZE,
-        seeds = [GlobalConfig::SEED_PREFIX],
+        let verifier = ctx.accounts.global_config.verifier;
+        let receipt = &mut ctx.accounts.construction_receipt;
+        receipt.initialize(
+            pool.key(),
+            milestone_index,
+            ctx.bumps.construction_receipt,
+            description_hash,
+            photo_hash,
+            &zone_id,
+            timestamp,
+        )?;
+
+        // The verifier must have signed this construction proof in the same transaction
+        let message = receipt.attestation_message();
+        receipt.signature =
+            require_ed25519_signature(&ctx.accounts.instructions, &verifier, &message)?;
+        receipt.verifier = verifier;
+
+        emit!(ConstructionReceiptSubmitted {
+            project_pool: receipt.project_pool,
+            milestone_index,
+            description_hash,
+            photo_hash,
+            verifier,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Remaining accounts carry one `(vault, builder token account)` pair per
+    /// material goal, in the pool's goal order.
+    pub fn release_milestone<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReleaseMilestone<'info>>,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.project_pool.key();
+        let builder = ctx.accounts.project_pool.builder;
+        let goals = ctx.accounts.project_pool.material_goals().to_vec();
+        let pairs = ctx.remaining_accounts;
+        if pairs.len() != goals.len() * 2 {
+            return Err(ErrorCode::MaterialVaultMissing.into());
+        }
+
+        // Each vault must be the pool's own PDA for the goal, paying the
+        // builder in the same mint
+        let mut vault_balances = Vec::with_capacity(goals.len());
+        for (goal, pair) in goals.iter().zip(pairs.chunks(2)) {
+            let vault = Account::<TokenAccount>::try_from(&pair[0])?;
+            let expected = Pubkey::create_program_address(
+                &[
+                    ProjectPool::VAULT_SEED_PREFIX,
+                    pool_key.as_ref(),
+                    &[goal.material_id],
+                    &[goal.vault_bump],
+                ],
+                ctx.program_id,
+            )
+            .map_err(|_| ErrorCode::MaterialVaultMissing)?;
+            if vault.key() != expected {
+                return Err(ErrorCode::MaterialVaultMissing.into());
+            }
+            let builder_account = Account::<TokenAccount>::try_from(&pair[1])?;
+            if builder_account.owner != builder {
+                return Err(ErrorCode::Unauthorized.into());
+            }
+            if builder_account.mint != goal.mint {
+                return Err(ErrorCode::MaterialMintMismatch.into());
+            }
+            vault_balances.push(vault.amount);
+        }
+
+        let (index, releases) = ctx
+            .accounts
+            .project_pool
+            .release_next_milestone(&vault_balances)?;
+
+        // Pay each material's tranche to the builder, signed by the pool PDA
+        let pool = &ctx.accounts.project_pool;
+        let id_bytes = pool.id.to_le_bytes();
+        let seeds = &[ProjectPool::SEED_PREFIX, &id_bytes, &[pool.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        for (pair, release) in pairs.chunks(2).zip(releases.iter()) {
+            if release.amount == 0 {
+                continue;
+            }
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: pair[0].clone(),
+                        to: pair[1].clone(),
+                        authority: ctx.accounts.project_pool.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                release.amount,
+            )?;
+        }
+
+        emit!(MilestoneReleased {
+            project_pool: pool.key(),
+            builder: pool.builder,
+            released_by: ctx.accounts.authority.key(),
+            milestone_index: index,
+            releases,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn claim_completion_badge(ctx: Context<ClaimCompletionBadge>) -> Result<()> {
+        let pool = &ctx.accounts.project_pool;
+        if pool.status != ProjectStatus::Completed {
+            return Err(ErrorCode::ProjectNotCompleted.into());
+        }
+        let contributed = ctx.accounts.contribution.amount;
+        let share_bps = pool.contribution_share_bps(contributed)?;
+        ctx.accounts.contribution.claim_badge()?;
+
+        let id_bytes = pool.id.to_le_bytes();
+        let seeds = &[ProjectPool::SEED_PREFIX, &id_bytes, &[pool.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        // Badge metadata points at the project's own curated page, which is
+        // frozen once the pool is funded; pools without one get a uri from
+        // their name
+        let mint_info = ctx.accounts.badge_mint.to_account_info();
+        let project_name = pool.name();
+        let uri = CompletionBadge::uri(ctx.accounts.project_metadata.as_deref(), &project_name);
+        let metadata = CompletionBadge::metadata(
+            pool.key(),
+            mint_info.key(),
+            &project_name,
+            uri,
+            contributed,
+            share_bps,
+        )?;
+
+        // Token-2022 grows the mint as metadata is written; fund the final size up front
+        let space = mint_info.data_len() + metadata.tlv_size_of()?;
+        let top_up = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(mint_info.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: mint_info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+
+        let token_program = ctx.accounts.token_2022_program.to_account_info();
+        let pool_info = ctx.accounts.project_pool.to_account_info();
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TokenMetadataInitialize {
+                    token_program_id: token_program.clone(),
+                    metadata: mint_info.clone(),
+                    update_authority: pool_info.clone(),
+                    mint_authority: pool_info.clone(),
+                    mint: mint_info.clone(),
+                },
+                signer_seeds,
+            ),
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        )?;
+        for (key, value) in metadata.additional_metadata {
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    TokenMetadataUpdateField {
+                        token_program_id: token_program.clone(),
+                        metadata: mint_info.clone(),
+                        update_authority: pool_info.clone(),
+                    },
+                    signer_seeds,
+                ),
+                Field::Key(key),
+                value,
+            )?;
+        }
+
+        // Mint the single badge and drop the mint authority so supply stays at one
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                token_interface::MintTo {
+                    mint: mint_info.clone(),
+                    to: ctx.accounts.badge_token_account.to_account_info(),
+                    authority: pool_info.clone(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )?;
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                token_program,
+                token_interface::SetAuthority {
+                    current_authority: pool_info,
+                    account_or_mint: mint_info,
+                },
+                signer_seeds,
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        emit!(CompletionBadgeClaimed {
+            player: ctx.accounts.authority.key(),
+            project_pool: ctx.accounts.project_pool.key(),
+            badge_mint: ctx.accounts.badge_mint.key(),
+            contributed,
+            share_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_poc_receipt(
+        ctx: Context<MintProofOfCollectionReceipt>,
+        attestation_id: [u8; 32],
+        photo_hash: [u8; 32],
+        zone_id: String,
+        material_type: u8,
+        quantity: u64,
+        timestamp: i64,
+        location: GeoPoint,
+    ) -> Result<()> {
+        ctx.accounts
+            .global_config
+            .require_not_paused(GlobalConfig::PAUSE_RECEIPTS)?;
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts
+            .global_config
+            .check_receipt_timestamp(timestamp, now)?;
+        ctx.accounts.material_config.require_usable(material_type)?;
+        // A receipt worth no BLOCK could never be minted and would strand the bond
+        ctx.accounts.material_config.blocks_for(quantity)?;
+        let player = &ctx.accounts.player_profile;
+
+        // Each photo can back a single receipt across all players
+        ctx.accounts.photo_hash_claim.claim(
+            photo_hash,
+            ctx.accounts.poc_receipt.key(),
+            player.authority,
+            ctx.bumps.photo_hash_claim,
+            now,
+        )?;
+
+        // The signed collection point must fall inside the named zone
+        if !ctx.accounts.zone.bounds.contains(location.lat_e7, location.lon_e7) {
+            return Err(ErrorCode::ReceiptOutsideZone.into());
+        }
+
+        let receipt = &mut ctx.accounts.poc_receipt;
+        receipt.initialize(
+            player.authority,
+            ctx.bumps.poc_receipt,
+            attestation_id,
+            photo_hash,
+            &zone_id,
+            location,
+            material_type,
+            quantity,
+            timestamp,
+        )?;
+        let config = &ctx.accounts.global_config;
+        let bond = config.dispute_bond;
+        receipt.open_challenge_window(now, config.challenge_period, bond)?;
+
+        // A quorum of registered verifiers must have signed the receipt hash
+        // through Ed25519 program instructions in this same transaction.
+        let receipt_hash = receipt.attestation_hash();
+        let signers = collect_ed25519_signers(&ctx.accounts.instructions, &receipt_hash)?;
+        receipt.approvals = ctx.accounts.verifier_registry.require_quorum(&signers)?;
+
+        // The submitter's bond sits on the receipt until it is minted or rejected
+        if bond > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: ctx.accounts.poc_receipt.to_account_info(),
+                    },
+                ),
+                bond,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn dispute_receipt(ctx: Context<DisputeReceipt>, reason_hash: [u8; 32]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.poc_receipt.open_dispute(now)?;
+
+        let bond = ctx.accounts.global_config.dispute_bond;
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.receipt = ctx.accounts.poc_receipt.key();
+        dispute.challenger = ctx.accounts.challenger.key();
+        dispute.bump = ctx.bumps.dispute;
+        dispute.bond = bond;
+        dispute.reason_hash = reason_hash;
+        dispute.opened_at = now;
+
+        if bond > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.challenger.to_account_info(),
+                        to: ctx.accounts.dispute.to_account_info(),
+                    },
+                ),
+                bond,
+            )?;
+        }
+
+        emit!(ReceiptDisputed {
+            receipt: ctx.accounts.poc_receipt.key(),
+            challenger: ctx.accounts.challenger.key(),
+            bond,
+            reason_hash,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, uphold: bool) -> Result<()> {
+        ctx.accounts.poc_receipt.resolve_dispute(uphold)?;
+
+        // Upheld: the challenger takes the submitter's bond. Dismissed: the
+        // submitter takes the challenger's. The dispute account then closes
+        // to the challenger, returning its rent and any bond left on it.
+        let payout = if uphold {
+            let bond = ctx.accounts.poc_receipt.take_bond();
+            move_lamports(
+                &ctx.accounts.poc_receipt.to_account_info(),
+                &ctx.accounts.challenger.to_account_info(),
+                bond,
+            )?;
+            bond
+        } else {
+            let bond = ctx.accounts.dispute.bond;
+            move_lamports(
+                &ctx.accounts.dispute.to_account_info(),
+                &ctx.accounts.player.to_account_info(),
+                bond,
+            )?;
+            bond
+        };
+
+        emit!(DisputeResolved {
+            receipt: ctx.accounts.poc_receipt.key(),
+            challenger: ctx.accounts.challenger.key(),
+            arbiter: ctx.accounts.arbiter.key(),
+            upheld: uphold,
+            payout,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Closes a receipt that can no longer be minted, returning its bond and
+    /// rent to the player.
+    pub fn close_unmintable_receipt(ctx: Context<CloseUnmintableReceipt>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let receipt = &ctx.accounts.poc_receipt;
+        receipt.require_unmintable(&ctx.accounts.material_config, now)?;
+
+        emit!(ReceiptClosed {
+            receipt: receipt.key(),
+            player: receipt.player,
+            bond: receipt.bond,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn initialize_config(ctx: Context<InitializeConfig>, verifier: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.global_config;
+        config.authority = ctx.accounts.authority.key();
+        config.pending_authority = Pubkey::default();
+        config.verifier = verifier;
+        config.curator = ctx.accounts.authority.key();
+        config.pauser = ctx.accounts.authority.key();
+        config.arbiter = ctx.accounts.authority.key();
+        config.paused = 0;
+        config.blocks_per_brick = DEFAULT_BLOCKS_PER_BRICK;
+        config.challenge_period = DEFAULT_CHALLENGE_PERIOD;
+        config.dispute_bond = DEFAULT_DISPUTE_BOND;
+        config.max_receipt_age = DEFAULT_MAX_RECEIPT_AGE;
+        config.max_future_skew = DEFAULT_MAX_FUTURE_SKEW;
+        config.block_mint = ctx.accounts.block_mint.key();
+        config.brick_mint = ctx.accounts.brick_mint.key();
+        config.total_blocks_minted = 0;
+        config.total_bricks_created = 0;
+        config.project_count = 0;
+        config.bump = ctx.bumps.global_config;
+        Ok(())
+    }
+
+    pub fn propose_authority(ctx: Context<AdminConfig>, new_authority: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.global_config;
+        config.pending_authority = new_authority;
+
+        emit!(AuthorityProposed {
+            authority: config.authority,
+            pending_authority: new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let config = &mut ctx.accounts.global_config;
+        let previous = config.authority;
+        config.accept_authority(ctx.accounts.new_authority.key())?;
+
+        emit!(AuthorityTransferred {
+            previous_authority: previous,
+            new_authority: config.authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn update_config(ctx: Context<AdminConfig>, params: ConfigParams) -> Result<()> {
+        let config = &mut ctx.accounts.global_config;
+        config.apply_params(&params)?;
+
+        emit!(ConfigUpdated {
+            authority: config.authority,
+            verifier: config.verifier,
+            curator: config.curator,
+            pauser: config.pauser,
+            arbiter: config.arbiter,
+            blocks_per_brick: config.blocks_per_brick,
+            challenge_period: config.challenge_period,
+            dispute_bond: config.dispute_bond,
+            max_receipt_age: config.max_receipt_age,
+            max_future_skew: config.max_future_skew,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn initialize_verifier_registry(
+        ctx: Context<InitializeVerifierRegistry>,
+        verifiers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.verifier_registry;
+        registry.initialize(ctx.bumps.verifier_registry, &verifiers, threshold)?;
+        emit_registry_updated(registry)
+    }
+
+    pub fn add_verifier(ctx: Context<AdminVerifierRegistry>, verifier: Pubkey) -> Result<()> {
+        let registry = &mut ctx.accounts.verifier_registry;
+        registry.add_verifier(verifier)?;
+        emit_registry_updated(registry)
+    }
+
+    pub fn remove_verifier(ctx: Context<AdminVerifierRegistry>, verifier: Pubkey) -> Result<()> {
+        let registry = &mut ctx.accounts.verifier_registry;
+        registry.remove_verifier(verifier)?;
+        emit_registry_updated(registry)
+    }
+
+    pub fn rotate_verifier(
+        ctx: Context<AdminVerifierRegistry>,
+        old_verifier: Pubkey,
+        new_verifier: Pubkey,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.verifier_registry;
+        registry.rotate_verifier(old_verifier, new_verifier)?;
+        emit_registry_updated(registry)
+    }
+
+    pub fn set_verifier_threshold(ctx: Context<AdminVerifierRegistry>, threshold: u8) -> Result<()> {
+        let registry = &mut ctx.accounts.verifier_registry;
+        registry.set_threshold(threshold)?;
+        emit_registry_updated(registry)
+    }
+
+    pub fn create_material(
+        ctx: Context<CreateMaterial>,
+        material_id: u8,
+        name: String,
+        units_per_block: u64,
+        mint: Option<Pubkey>,
+    ) -> Result<()> {
+        let material = &mut ctx.accounts.material_config;
+        material.initialize(material_id, ctx.bumps.material_config, &name, units_per_block, mint)?;
+
+        emit!(MaterialCreated {
+            material_id,
+            name,
+            units_per_block,
+            mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn update_material(
+        ctx: Context<UpdateMaterial>,
+        active: bool,
+        units_per_block: u64,
+        mint: Option<Pubkey>,
+    ) -> Result<()> {
+        let material = &mut ctx.accounts.material_config;
+        material.update(active, units_per_block, mint)?;
+
+        emit!(MaterialUpdated {
+            material_id: material.material_id,
+            active,
+            units_per_block,
+            mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn create_zone(
+        ctx: Context<CreateZone>,
+        zone_id: String,
+        name: String,
+        bounds: ZoneBounds,
+    ) -> Result<()> {
+        let zone = &mut ctx.accounts.zone;
+        zone.initialize(ctx.bumps.zone, &zone_id, &name, bounds)?;
+
+        emit!(ZoneCreated {
+            zone_id,
+            name,
+            bounds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn update_zone(ctx: Context<UpdateZone>, active: bool, bounds: ZoneBounds) -> Result<()> {
+        let zone = &mut ctx.accounts.zone;
+        zone.update(active, bounds)?;
+
+        emit!(ZoneUpdated {
+            zone_id: zone.zone_id(),
+            active,
+            bounds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn pause(ctx: Context<PauserConfig>, flags: u8) -> Result<()> {
+        let config = &mut ctx.accounts.global_config;
+        config.set_paused(flags, true)?;
+
+        emit!(ProgramPaused {
+            pauser: ctx.accounts.pauser.key(),
+            flags,
+            paused: config.paused,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn unpause(ctx: Context<PauserConfig>, flags: u8) -> Result<()> {
+        let config = &mut ctx.accounts.global_config;
+        config.set_paused(flags, false)?;
+
+        emit!(ProgramUnpaused {
+            pauser: ctx.accounts.pauser.key(),
+            flags,
+            paused: config.paused,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn mint_blocks(ctx: Context<MintBlocks>) -> Result<()> {
+        ctx.accounts
+            .global_config
+            .require_not_paused(GlobalConfig::PAUSE_MINT_BLOCKS)?;
+
+        // Consume the receipt first so it can never back a second mint
+        let receipt = &mut ctx.accounts.poc_receipt;
+        let amount = receipt.redeem(&ctx.accounts.material_config, Clock::get()?.unix_timestamp)?;
+        let waste_type = receipt.material_type;
+
+        // Only receipts that survived the challenge window count toward zone stats
+        ctx.accounts
+            .zone
+            .record_collection(waste_type, receipt.quantity)?;
+
+        // The window closed undisputed; hand the submitter's bond back
+        let bond = receipt.take_bond();
+        move_lamports(
+            &ctx.accounts.poc_receipt.to_account_info(),
+            &ctx.accounts.player_authority.to_account_info(),
+            bond,
+        )?;
+
+        // Mint BLOCK tokens to player's ATA using GlobalConfig PDA as mint authority
+        let seeds = &[
+            GlobalConfig::SEED_PREFIX,
+            &[ctx.accounts.global_config.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.block_mint.to_account_info(),
+                    to: ctx.accounts.player_token_account.to_account_info(),
+                    authority: ctx.accounts.global_config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        // Initialize player profile if newly created via init_if_needed
+        let player = &mut ctx.accounts.player_profile;
+        if player.authority == Pubkey::default() {
+            player.authority = ctx.accounts.player_authority.key();
+            player.bump = ctx.bumps.player_profile;
+            player.credits_contributed = 0;
+            player.blocks_minted = 0;
+            player.brick_count = 0;
+            player.collections_count = 0;
+        }
+
+        // Update player stats
+        player.record_mint(amount)?;
+
+        // Update global stats
+        let config = &mut ctx.accounts.global_config;
+        config.total_blocks_minted = config
+            .total_blocks_minted
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        emit!(BlocksMinted {
+            player: ctx.accounts.player_profile.authority,
+            receipt: ctx.accounts.poc_receipt.key(),
+            amount,
+            waste_type,
+            units_per_block: ctx.accounts.material_config.units_per_block,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn convert_to_brick(ctx: Context<ConvertToBrick>, count: u64) -> Result<()> {
+        ctx.accounts
+            .global_config
+            .require_not_paused(GlobalConfig::PAUSE_CONVERT_TO_BRICK)?;
+
+        // Check balance against the current rate
+        let blocks_per_brick = ctx.accounts.global_config.blocks_per_brick;
+        let blocks_burned = ctx.accounts.global_config.blocks_for_bricks(count)?;
+        if ctx.accounts.player_token_account.amount < blocks_burned {
+            return Err(ErrorCode::InsufficientBlocks.into());
+        }
+
+        // Burn `count` bricks' worth of BLOCK tokens from player's ATA in one CPI
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.block_mint.to_account_info(),
+                    from: ctx.accounts.player_token_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            blocks_burned,
+        )?;
+
+        // Mint `count` BRICK to player's BRICK ATA using GlobalConfig PDA as mint authority
+        let seeds = &[
+            GlobalConfig::SEED_PREFIX,
+            &[ctx.accounts.global_config.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.brick_mint.to_account_info(),
+                    to: ctx.accounts.player_brick_account.to_account_info(),
+                    authority: ctx.accounts.global_config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            count,
+        )?;
+
+        // Update player stats
+        let player = &mut ctx.accounts.player_profile;
+        player.brick_count = player
+            .brick_count
+            .checked_add(count)
+            .ok_or(ErrorCode::Overflow)?;
+
+        // Update global stats
+        let config = &mut ctx.accounts.global_config;
+        config.total_bricks_created = config
+            .total_bricks_created
+            .checked_add(count)
+            .ok_or(ErrorCode::Overflow)?;
+
+        emit!(BrickConverted {
+            player: player.authority,
+            count,
+            new_brick_count: player.brick_count,
+            blocks_burned,
+            blocks_per_brick,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+// ── Account contexts ──────────────────────────────────────────────────
+
+#[derive(Accounts)]
+pub struct InitializePlayer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = PlayerProfile::SIZE,
+        seeds = [PlayerProfile::SEED_PREFIX, authority.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProjectPool<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_config.curator @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = ProjectPool::SIZE,
+        seeds = [ProjectPool::SEED_PREFIX, &global_config.project_count.to_le_bytes()],
+        bump
+    )]
+    pub project_pool: Account<'info, ProjectPool>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(material_id: u8)]
+pub struct InitMaterialVault<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == project_pool.authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [ProjectPool::SEED_PREFIX, &project_pool.id.to_le_bytes()],
+        bump = project_pool.bump
+    )]
+    pub project_pool: Account<'info, ProjectPool>,
+    #[account(
+        seeds = [MaterialConfig::SEED_PREFIX, &[material_id]],
+        bump = material_config.bump,
+        constraint = material_config.mint == Some(vault_mint.key())
+            @ ErrorCode::MaterialMintMismatch
+    )]
+    pub material_config: Account<'info, MaterialConfig>,
+    /// The material's dedicated mint; the vault only ever holds this token.
+    pub vault_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = vault_mint,
+        token::authority = project_pool,
+        seeds = [ProjectPool::VAULT_SEED_PREFIX, project_pool.key().as_ref(), &[material_id]],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, material_id: u8)]
+pub struct ContributeCredits<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [PlayerProfile::SEED_PREFIX, authority.key().as_ref()],
+        bump = player_profile.bump,
+        constraint = player_profile.authority == authority.key()
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    #[account(
+        mut,
+        seeds = [ProjectPool::SEED_PREFIX, &project_pool.id.to_le_bytes()],
+        bump = project_pool.bump
+    )]
+    pub project_pool: Account<'info, ProjectPool>,
+    #[account(
+        mut,
+        seeds = [ProjectPool::VAULT_SEED_PREFIX, project_pool.key().as_ref(), &[material_id]],
+        bump,
+        constraint = project_pool.goal_mint(material_id) == Some(vault.mint)
+            @ ErrorCode::MaterialMintMismatch
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [MaterialConfig::SEED_PREFIX, &[material_id]],
+        bump = material_config.bump
+    )]
+    pub material_config: Account<'info, MaterialConfig>,
+    #[account(
+        mut,
+        token::mint = vault.mint,
+        token::authority = authority,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Contribution::SIZE,
+        seeds = [
+            Contribution::SEED_PREFIX,
+            project_pool.key().as_ref(),
+            authority.key().as_ref()
+        ],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(material_id: u8)]
+pub struct ClaimRefund<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [ProjectPool::SEED_PREFIX, &project_pool.id.to_le_bytes()],
+        bump = project_pool.bump
+    )]
+    pub project_pool: Account<'info, ProjectPool>,
+    #[account(
+        mut,
+        seeds = [ProjectPool::VAULT_SEED_PREFIX, project_pool.key().as_ref(), &[material_id]],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            Contribution::SEED_PREFIX,
+            project_pool.key().as_ref(),
+            authority.key().as_ref()
+        ],
+        bump = contribution.bump,
+        constraint = contribution.contributor == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub contribution: Account<'info, Contribution>,
+    #[account(
+        mut,
+        token::mint = vault.mint,
+        token::authority = authority,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ManageProject<'info> {
+    #[account(
+        constraint = authority.key() == project_pool.authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [ProjectPool::SEED_PREFIX, &project_pool.id.to_le_bytes()],
+        bump = project_pool.bump
+    )]
+    pub project_pool: Account<'info, ProjectPool>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProjectMetadata<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == project_pool.authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [ProjectPool::SEED_PREFIX, &project_pool.id.to_le_bytes()],
+        bump = project_pool.bump
+    )]
+    pub project_pool: Account<'info, ProjectPool>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ProjectMetadata::SIZE,
+        seeds = [ProjectMetadata::SEED_PREFIX, project_pool.key().as_ref()],
+        bump
+    )]
+    pub project_metadata: Account<'info, ProjectMetadata>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(milestone_index: u8)]
+pub struct SubmitConstructionReceipt<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [ProjectPool::SEED_PREFIX, &project_pool.id.to_le_bytes()],
+        bump = project_pool.bump
+    )]
+    pub project_pool: Account<'info, ProjectPool>,
+    #[account(
+        init,
+        payer = payer,
+        space = ConstructionReceipt::SIZE,
+        seeds = [
+            ConstructionReceipt::SEED_PREFIX,
+            project_pool.key().as_ref(),
+            &[milestone_index]
+        ],
+        bump
+    )]
+    pub construction_receipt: Account<'info, ConstructionReceipt>,
+    /// CHECK: Instructions sysvar, used to find the verifier's Ed25519 signature.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseMilestone<'info> {
+    #[account(
+        constraint = authority.key() == global_config.curator
+            || authority.key() == global_config.verifier @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [ProjectPool::SEED_PREFIX, &project_pool.id.to_le_bytes()],
+        bump = project_pool.bump
+    )]
+    pub project_pool: Account<'info, ProjectPool>,
+    /// Proof of construction for the milestone being released.
+    #[account(
+        seeds = [
+            ConstructionReceipt::SEED_PREFIX,
+            project_pool.key().as_ref(),
+            &[project_pool.milestones_released]
+        ],
+        bump = construction_receipt.bump
+    )]
+    pub construction_receipt: Account<'info, ConstructionReceipt>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCompletionBadge<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [ProjectPool::SEED_PREFIX, &project_pool.id.to_le_bytes()],
+        bump = project_pool.bump
+    )]
+    pub project_pool: Account<'info, ProjectPool>,
+    #[account(
+        mut,
+        seeds = [
+            Contribution::SEED_PREFIX,
+            project_pool.key().as_ref(),
+            authority.key().as_ref()
+        ],
+        bump = contribution.bump,
+        constraint = contribution.contributor == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub contribution: Account<'info, Contribution>,
+    /// Only present if the pool authority ever wrote project metadata.
+    #[account(
+        seeds = [ProjectMetadata::SEED_PREFIX, project_pool.key().as_ref()],
+        bump = project_metadata.bump
+    )]
+    pub project_metadata: Option<Account<'info, ProjectMetadata>>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [CompletionBadge::SEED_PREFIX, contribution.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = project_pool,
+        mint::token_program = token_2022_program,
+        extensions::metadata_pointer::authority = project_pool,
+        extensions::metadata_pointer::metadata_address = badge_mint,
+    )]
+    pub badge_mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = badge_mint,
+        associated_token::authority = authority,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub badge_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptProjectAuthority<'info> {
+    #[account(
+        constraint = new_authority.key() == project_pool.pending_authority @ ErrorCode::Unauthorized
+    )]
+    pub new_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [ProjectPool::SEED_PREFIX, &project_pool.id.to_le_bytes()],
+        bump = project_pool.bump
+    )]
+    pub project_pool: Account<'info, ProjectPool>,
+}
+
+impl ManageProject<'_> {
+    fn set_status(&mut self, to: ProjectStatus) -> Result<()> {
+        let pool = &mut self.project_pool;
+        let from = pool.transition(to)?;
+
+        emit!(ProjectStatusChanged {
+            project_pool: pool.key(),
+            from,
+            to,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(attestation_id: [u8; 32], photo_hash: [u8; 32], zone_id: String, material_type: u8)]
+pub struct MintProofOfCollectionReceipt<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [PlayerProfile::SEED_PREFIX, authority.key().as_ref()],
+        bump = player_profile.bump,
+        constraint = player_profile.authority == authority.key()
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    #[account(
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        seeds = [MaterialConfig::SEED_PREFIX, &[material_type]],
+        bump = material_config.bump
+    )]
+    pub material_config: Account<'info, MaterialConfig>,
+    #[account(
+        seeds = [Zone::SEED_PREFIX, zone_id.as_bytes()],
+        bump = zone.bump,
+        constraint = zone.active @ ErrorCode::ZoneInactive
+    )]
+    pub zone: Box<Account<'info, Zone>>,
+    #[account(
+        init,
+        payer = authority,
+        space = ProofOfCollectionReceipt::SIZE,
+        seeds = [
+            ProofOfCollectionReceipt::SEED_PREFIX,
+            player_profile.authority.as_ref(),
+            &attestation_id
+        ],
+        bump
+    )]
+    pub poc_receipt: Account<'info, ProofOfCollectionReceipt>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PhotoHashClaim::SIZE,
+        seeds = [PhotoHashClaim::SEED_PREFIX, &photo_hash],
+        bump
+    )]
+    pub photo_hash_claim: Account<'info, PhotoHashClaim>,
+    #[account(
+        seeds = [VerifierRegistry::SEED_PREFIX],
+        bump = verifier_registry.bump
+    )]
+    pub verifier_registry: Account<'info, VerifierRegistry>,
+    /// CHECK: Instructions sysvar, used to find the verifiers' Ed25519 signatures.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = GlobalConfig::SIZE,
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 0,
+        mint::authority = global_config,
+        seeds = [b"block_mint"],
+        bump
+    )]
+    pub block_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 0,
+        mint::authority = global_config,
+        seeds = [b"brick_mint"],
+        bump
+    )]
+    pub brick_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct AdminConfig<'info> {
+    #[account(
+        constraint = authority.key() == global_config.authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeReceipt<'info> {
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    #[account(
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [
+            ProofOfCollectionReceipt::SEED_PREFIX,
+            poc_receipt.player.as_ref(),
+            &poc_receipt.attestation_id
+        ],
+        bump = poc_receipt.bump
+    )]
+    pub poc_receipt: Account<'info, ProofOfCollectionReceipt>,
+    #[account(
+        init,
+        payer = challenger,
+        space = Dispute::SIZE,
+        seeds = [Dispute::SEED_PREFIX, poc_receipt.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        constraint = arbiter.key() == global_config.arbiter @ ErrorCode::Unauthorized
+    )]
+    pub arbiter: Signer<'info>,
+    #[account(
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [
+            ProofOfCollectionReceipt::SEED_PREFIX,
+            poc_receipt.player.as_ref(),
+            &poc_receipt.attestation_id
+        ],
+        bump = poc_receipt.bump
+    )]
+    pub poc_receipt: Account<'info, ProofOfCollectionReceipt>,
+    #[account(
+        mut,
+        close = challenger,
+        seeds = [Dispute::SEED_PREFIX, poc_receipt.key().as_ref()],
+        bump = dispute.bump,
+        has_one = challenger
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut)]
+    pub challenger: SystemAccount<'info>,
+    /// Receipt submitter; receives the challenger's bond if the dispute is dismissed.
+    #[account(mut, address = poc_receipt.player)]
+    pub player: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseUnmintableReceipt<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            ProofOfCollectionReceipt::SEED_PREFIX,
+            authority.key().as_ref(),
+            &poc_receipt.attestation_id
+        ],
+        bump = poc_receipt.bump
+    )]
+    pub poc_receipt: Account<'info, ProofOfCollectionReceipt>,
+    #[account(
+        seeds = [MaterialConfig::SEED_PREFIX, &[poc_receipt.material_type]],
+        bump = material_config.bump
+    )]
+    pub material_config: Account<'info, MaterialConfig>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVerifierRegistry<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_config.authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = VerifierRegistry::SIZE,
+        seeds = [VerifierRegistry::SEED_PREFIX],
+        bump
+    )]
+    pub verifier_registry: Account<'info, VerifierRegistry>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdminVerifierRegistry<'info> {
+    #[account(
+        constraint = authority.key() == global_config.authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [VerifierRegistry::SEED_PREFIX],
+        bump = verifier_registry.bump
+    )]
+    pub verifier_registry: Account<'info, VerifierRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        constraint = new_authority.key() == global_config.pending_authority @ ErrorCode::Unauthorized
+    )]
+    pub new_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(material_id: u8)]
+pub struct CreateMaterial<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_config.authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = MaterialConfig::SIZE,
+        seeds = [MaterialConfig::SEED_PREFIX, &[material_id]],
         bump
     )]
+    pub material_config: Account<'info, MaterialConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMaterial<'info> {
+    #[account(
+        constraint = authority.key() == global_config.authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [MaterialConfig::SEED_PREFIX, &[material_config.material_id]],
+        bump = material_config.bump
+    )]
+    pub material_config: Account<'info, MaterialConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(zone_id: String)]
+pub struct CreateZone<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_config.authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
     pub global_config: Account<'info, GlobalConfig>,
     #[account(
         init,
         payer = authority,
-        mint::decimals = 0,
-        mint::authority = global_config,
+        space = Zone::SIZE,
+        seeds = [Zone::SEED_PREFIX, zone_id.as_bytes()],
+        bump
+    )]
+    pub zone: Box<Account<'info, Zone>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateZone<'info> {
+    #[account(
+        constraint = authority.key() == global_config.authority @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [Zone::SEED_PREFIX, &zone.zone_id[..zone.zone_id_len as usize]],
+        bump = zone.bump
+    )]
+    pub zone: Box<Account<'info, Zone>>,
+}
+
+#[derive(Accounts)]
+pub struct PauserConfig<'info> {
+    #[account(
+        constraint = pauser.key() == global_config.pauser @ ErrorCode::Unauthorized
+    )]
+    pub pauser: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[derive(Accounts)]
+pub struct MintBlocks<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_config.verifier @ ErrorCode::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
         seeds = [b"block_mint"],
+        bump,
+        constraint = block_mint.key() == global_config.block_mint
+    )]
+    pub block_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PlayerProfile::SIZE,
+        seeds = [PlayerProfile::SEED_PREFIX, player_authority.key().as_ref()],
         bump
     )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    #[account(
+        mut,
+        seeds = [
+            ProofOfCollectionReceipt::SEED_PREFIX,
+            player_authority.key().as_ref(),
+            &poc_receipt.attestation_id
+        ],
+        bump = poc_receipt.bump,
+        constraint = !poc_receipt.redeemed @ ErrorCode::ReceiptAlreadyRedeemed
+    )]
+    pub poc_receipt: Account<'info, ProofOfCollectionReceipt>,
+    #[account(
+        seeds = [MaterialConfig::SEED_PREFIX, &[poc_receipt.material_type]],
+        bump = material_config.bump
+    )]
+    pub material_config: Account<'info, MaterialConfig>,
+    #[account(
+        mut,
+        seeds = [Zone::SEED_PREFIX, &poc_receipt.zone_id[..poc_receipt.zone_id_len as usize]],
+        bump = zone.bump
+    )]
+    pub zone: Box<Account<'info, Zone>>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = block_mint,
+        associated_token::authority = player_authority,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+    /// CHECK: The player's wallet pubkey, used to derive player_profile PDA and
+    /// ATA; receives the receipt bond back.
+    #[account(mut)]
+    pub player_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConvertToBrick<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [GlobalConfig::SEED_PREFIX],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        seeds = [b"block_mint"],
+        bump,
+        constraint = block_mint.key() == global_config.block_mint
+    )]
     pub block_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"brick_mint"],
+        bump,
+        constraint = brick_mint.key() == global_config.brick_mint
+    )]
+    pub brick_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [PlayerProfile::SEED_PREFIX, authority.key().as_ref()],
+        bump = player_profile.bump,
+        constraint = player_profile.authority == authority.key()
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    #[account(
+        mut,
+        associated_token::mint = block_mint,
+        associated_token::authority = authority,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = brick_mint,
+        associated_token::authority = authority,
+    )]
+    pub player_brick_account: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
-#[derive(Accounts)]
-pub struct MintBlocks<'info> {
-    #[account(
-        mut,
-        constraint = authority.key() == global_config.authority @ ErrorCode::Unauthorized
-    )]
-    pub authority: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [GlobalConfig::SEED_PREFIX],
-        bump = global_config.bump
-    )]
-    pub global_config: Account<'info, GlobalConfig>,
-    #[account(
-        mut,
-        seeds = [b"block_mint"],
-        bump,
-        constraint = block_mint.key() == global_config.block_mint
-    )]
-    pub block_mint: Account<'info, Mint>,
-    #[account(
-        init_if_needed,
-        payer = authority,
-        space = PlayerProfile::SIZE,
-        seeds = [PlayerProfile::SEED_PREFIX, player_authority.key().as_ref()],
-        bump
-    )]
-    pub player_profile: Account<'info, PlayerProfile>,
-    #[account(
-        init_if_needed,
-        payer = authority,
-        associated_token::mint = block_mint,
-        associated_token::authority = player_authority,
-    )]
-    pub player_token_account: Account<'info, TokenAccount>,
-    /// CHECK: The player's wallet pubkey, used to derive player_profile PDA and ATA.
-    pub player_authority: UncheckedAccount<'info>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
+// ── Account structs ───────────────────────────────────────────────────
+
+#[account]
+pub struct GlobalConfig {
+    /// Admin role: config changes and authority hand-off.
+    pub authority: Pubkey,
+    /// Proposed next authority; `Pubkey::default()` when no transfer is pending.
+    pub pending_authority: Pubkey,
+    /// Minter/verifier role: mints BLOCK and signs construction receipts;
+    /// collection receipts are approved by the `VerifierRegistry` quorum.
+    pub verifier: Pubkey,
+    /// Curator role: creates project pools.
+    pub curator: Pubkey,
+    /// Pauser role: halts program operations in an emergency.
+    pub pauser: Pubkey,
+    /// Arbiter role: resolves disputes raised against receipts.
+    pub arbiter: Pubkey,
+    /// Bitmask of paused operations, see the `PAUSE_*` flags.
+    pub paused: u8,
+    /// BLOCK burned per brick by `convert_to_brick`.
+    pub blocks_per_brick: u64,
+    /// Seconds a new receipt can be disputed before its BLOCK can be minted.
+    pub challenge_period: i64,
+    /// Lamports staked by receipt submitters and by challengers.
+    pub dispute_bond: u64,
+    /// Receipts older than this many seconds are rejected as stale.
+    pub max_receipt_age: i64,
+    /// Receipts dated more than this many seconds ahead of the clock are rejected.
+    pub max_future_skew: i64,
+    pub block_mint: Pubkey,
+    pub brick_mint: Pubkey,
+    pub total_blocks_minted: u64,
+    pub total_bricks_created: u64,
+    /// Number of project pools created; the next pool takes this as its id.
+    pub project_count: u64,
+    pub bump: u8,
+}
+
+impl GlobalConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"global_config";
+    pub const SIZE: usize = 8  // discriminator
+        + 32                   // authority
+        + 32                   // pending_authority
+        + 32                   // verifier
+        + 32                   // curator
+        + 32                   // pauser
+        + 32                   // arbiter
+        + 1                    // paused
+        + 8                    // blocks_per_brick
+        + 8                    // challenge_period
+        + 8                    // dispute_bond
+        + 8                    // max_receipt_age
+        + 8                    // max_future_skew
+        + 32                   // block_mint
+        + 32                   // brick_mint
+        + 8                    // total_blocks_minted
+        + 8                    // total_bricks_created
+        + 8                    // project_count
+        + 1;                   // bump
+
+    pub const PAUSE_MINT_BLOCKS: u8 = 1 << 0;
+    pub const PAUSE_CONVERT_TO_BRICK: u8 = 1 << 1;
+    pub const PAUSE_CONTRIBUTE: u8 = 1 << 2;
+    pub const PAUSE_RECEIPTS: u8 = 1 << 3;
+    pub const PAUSE_ALL: u8 = Self::PAUSE_MINT_BLOCKS
+        | Self::PAUSE_CONVERT_TO_BRICK
+        | Self::PAUSE_CONTRIBUTE
+        | Self::PAUSE_RECEIPTS;
+
+    /// BLOCK needed to convert `count` bricks at the current rate.
+    pub fn blocks_for_bricks(&self, count: u64) -> Result<u64> {
+        if count == 0 {
+            return Err(ErrorCode::InvalidAmount.into());
+        }
+        count
+            .checked_mul(self.blocks_per_brick)
+            .ok_or(ErrorCode::Overflow.into())
+    }
+
+    pub fn require_not_paused(&self, flag: u8) -> Result<()> {
+        if self.paused & flag != 0 {
+            return Err(ErrorCode::Paused.into());
+        }
+        Ok(())
+    }
+
+    pub fn set_paused(&mut self, flags: u8, paused: bool) -> Result<()> {
+        if flags == 0 || flags & !Self::PAUSE_ALL != 0 {
+            return Err(ErrorCode::InvalidPauseFlags.into());
+        }
+        if paused {
+            self.paused |= flags;
+        } else {
+            self.paused &= !flags;
+        }
+        Ok(())
+    }
+
+    /// Hands out the next sequential project id.
+    pub fn next_project_id(&mut self) -> Result<u64> {
+        let id = self.project_count;
+        self.project_count = id.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        Ok(id)
+    }
+
+    pub fn accept_authority(&mut self, signer: Pubkey) -> Result<()> {
+        if self.pending_authority == Pubkey::default() || self.pending_authority != signer {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        self.authority = signer;
+        self.pending_authority = Pubkey::default();
+        Ok(())
+    }
+
+    pub fn apply_params(&mut self, params: &ConfigParams) -> Result<()> {
+        for (role, value) in [
+            (&mut self.verifier, params.verifier),
+            (&mut self.curator, params.curator),
+            (&mut self.pauser, params.pauser),
+            (&mut self.arbiter, params.arbiter),
+        ] {
+            if let Some(key) = value {
+                if key == Pubkey::default() {
+                    return Err(ErrorCode::InvalidConfigParam.into());
+                }
+                *role = key;
+            }
+        }
+        if let Some(blocks_per_brick) = params.blocks_per_brick {
+            if blocks_per_brick == 0 {
+                return Err(ErrorCode::InvalidConfigParam.into());
+            }
+            self.blocks_per_brick = blocks_per_brick;
+        }
+        if let Some(challenge_period) = params.challenge_period {
+            if challenge_period < 0 {
+                return Err(ErrorCode::InvalidConfigParam.into());
+            }
+            self.challenge_period = challenge_period;
+        }
+        if let Some(dispute_bond) = params.dispute_bond {
+            self.dispute_bond = dispute_bond;
+        }
+        if let Some(max_receipt_age) = params.max_receipt_age {
+            if max_receipt_age <= 0 {
+                return Err(ErrorCode::InvalidConfigParam.into());
+            }
+            self.max_receipt_age = max_receipt_age;
+        }
+        if let Some(max_future_skew) = params.max_future_skew {
+            if max_future_skew < 0 {
+                return Err(ErrorCode::InvalidConfigParam.into());
+            }
+            self.max_future_skew = max_future_skew;
+        }
+        Ok(())
+    }
+
+    /// Requires a receipt timestamp within `max_receipt_age` before `now` and
+    /// no more than `max_future_skew` after it.
+    pub fn check_receipt_timestamp(&self, timestamp: i64, now: i64) -> Result<()> {
+        if timestamp > now.saturating_add(self.max_future_skew) {
+            return Err(ErrorCode::ReceiptTimestampInFuture.into());
+        }
+        if timestamp < now.saturating_sub(self.max_receipt_age) {
+            return Err(ErrorCode::ReceiptTimestampStale.into());
+        }
+        Ok(())
+    }
+}
+
+/// Lifetime player stats. Spendable value lives in the player's token
+/// accounts (BLOCK, BRICK and material mints), so there is no separate
+/// credit balance to keep in sync.
+#[account]
+pub struct PlayerProfile {
+    pub authority: Pubkey,
+    pub bump: u8,
+    /// Lifetime tokens contributed to project pools.
+    pub credits_contributed: u64,
+    pub blocks_minted: u64,
+    pub brick_count: u64,
+    pub collections_count: u64,
+}
+
+impl PlayerProfile {
+    pub const SEED_PREFIX: &'static [u8] = b"player";
+    pub const SIZE: usize = 8  // discriminator
+        + 32                   // authority pubkey
+        + 1                    // bump
+        + 8                    // credits_contributed
+        + 8                    // blocks_minted
+        + 8                    // brick_count
+        + 8;                   // collections_count
+
+    pub fn initialize(&mut self, authority: Pubkey, bump: u8) -> Result<()> {
+        self.authority = authority;
+        self.bump = bump;
+        self.credits_contributed = 0;
+        self.blocks_minted = 0;
+        self.brick_count = 0;
+        self.collections_count = 0;
+        Ok(())
+    }
+
+    /// Records a redeemed receipt worth `amount` BLOCK.
+    pub fn record_mint(&mut self, amount: u64) -> Result<()> {
+        self.blocks_minted = self
+            .blocks_minted
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        self.collections_count = self
+            .collections_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    pub fn record_contribution(&mut self, amount: u64) -> Result<()> {
+        self.credits_contributed = self
+            .credits_contributed
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct ProjectPool {
+    pub authority: Pubkey,
+    /// Proposed successor; must accept before taking over the pool.
+    pub pending_authority: Pubkey,
+    pub bump: u8,
+    /// Sequential id assigned from `GlobalConfig::project_count`; seeds the PDA.
+    pub id: u64,
+    /// Sum of all material targets.
+    pub goal_credits: u64,
+    /// Sum of the material vault balances; the vaults are the source of truth.
+    pub received_credits: u64,
+    pub name_len: u8,
+    pub name: [u8; ProjectPool::NAME_MAX_LEN],
+    pub status: ProjectStatus,
+    /// Unix time after which an under-goal pool stops taking contributions
+    /// and becomes refundable.
+    pub deadline: i64,
+    pub material_goals_len: u8,
+    pub material_goals: [MaterialGoal; ProjectPool::MAX_MATERIAL_GOALS],
+    /// Account that receives milestone releases.
+    pub builder: Pubkey,
+    pub milestones_len: u8,
+    pub milestones: [Milestone; ProjectPool::MAX_MILESTONES],
+    /// Milestones are released in order; this is the next index to release.
+    pub milestones_released: u8,
+    pub released_credits: u64,
+}
+
+impl ProjectPool {
+    pub const SEED_PREFIX: &'static [u8] = b"project";
+    pub const VAULT_SEED_PREFIX: &'static [u8] = b"vault";
+    pub const NAME_MAX_LEN: usize = 32;
+    pub const MAX_MATERIAL_GOALS: usize = 8;
+    pub const MAX_MILESTONES: usize = 8;
+    pub const BPS_DENOMINATOR: u64 = 10_000;
+    pub const SIZE: usize = 8  // discriminator
+        + 32                   // authority
+        + 32                   // pending authority
+        + 1                    // bump
+        + 8                    // id
+        + 8                    // goal credits
+        + 8                    // received credits
+        + 1                    // name length
+        + Self::NAME_MAX_LEN   // name bytes
+        + 1                    // status
+        + 8                    // deadline
+        + 1                    // material goals length
+        + Self::MAX_MATERIAL_GOALS * MaterialGoal::SIZE // material goals
+        + 32                   // builder
+        + 1                    // milestones length
+        + Self::MAX_MILESTONES * Milestone::SIZE // milestones
+        + 1                    // milestones released
+        + 8;                   // released credits
+
+    pub fn initialize(
+        &mut self,
+        authority: Pubkey,
+        bump: u8,
+        id: u64,
+        targets: &[MaterialTarget],
+        name: &str,
+        deadline: i64,
+    ) -> Result<()> {
+        if targets.is_empty() || targets.len() > Self::MAX_MATERIAL_GOALS {
+            return Err(ErrorCode::InvalidMaterialGoals.into());
+        }
+        if name.len() > Self::NAME_MAX_LEN {
+            return Err(ErrorCode::NameTooLong.into());
+        }
+
+        let mut goal: u64 = 0;
+        self.material_goals = [MaterialGoal::default(); Self::MAX_MATERIAL_GOALS];
+        for (i, target) in targets.iter().enumerate() {
+            if target.target == 0 {
+                return Err(ErrorCode::InvalidAmount.into());
+            }
+            if targets[..i].iter().any(|t| t.material_id == target.material_id) {
+                return Err(ErrorCode::InvalidMaterialGoals.into());
+            }
+            goal = goal.checked_add(target.target).ok_or(ErrorCode::Overflow)?;
+            self.material_goals[i] = MaterialGoal {
+                material_id: target.material_id,
+                target: target.target,
+                ..MaterialGoal::default()
+            };
+        }
+        self.material_goals_len = targets.len() as u8;
+        self.builder = Pubkey::default();
+        self.milestones_len = 0;
+        self.milestones = [Milestone::default(); Self::MAX_MILESTONES];
+        self.milestones_released = 0;
+        self.released_credits = 0;
+
+        self.authority = authority;
+        self.pending_authority = Pubkey::default();
+        self.bump = bump;
+        self.id = id;
+        self.goal_credits = goal;
+        self.received_credits = 0;
+        self.name_len = name.len() as u8;
+        self.name = [0u8; Self::NAME_MAX_LEN];
+        self.name[..name.len()].copy_from_slice(name.as_bytes());
+        self.status = ProjectStatus::Draft;
+        self.deadline = deadline;
+        Ok(())
+    }
+
+    pub fn material_goals(&self) -> &[MaterialGoal] {
+        &self.material_goals[..self.material_goals_len as usize]
+    }
+
+    /// Position of a material in the pool's goals; contributions are
+    /// tracked per slot.
+    pub fn goal_index(&self, material_id: u8) -> Result<usize> {
+        self.material_goals()
+            .iter()
+            .position(|g| g.material_id == material_id)
+            .ok_or(ErrorCode::MaterialNotInProject.into())
+    }
+
+    /// Mint the material goal's vault was created for, if it has one. Later
+    /// registry changes to the material's mint do not affect existing pools.
+    pub fn goal_mint(&self, material_id: u8) -> Option<Pubkey> {
+        self.material_goals()
+            .iter()
+            .find(|g| g.material_id == material_id && g.mint != Pubkey::default())
+            .map(|g| g.mint)
+    }
+
+    /// Records the vault created for a material goal. Vaults can only be
+    /// added while the pool is still a draft.
+    pub fn bind_material_vault(
+        &mut self,
+        material_id: u8,
+        mint: Pubkey,
+        vault_bump: u8,
+    ) -> Result<()> {
+        if self.status != ProjectStatus::Draft {
+            return Err(ErrorCode::MaterialVaultsLocked.into());
+        }
+        let slot = self.goal_index(material_id)?;
+        let goal = &mut self.material_goals[slot];
+        goal.mint = mint;
+        goal.vault_bump = vault_bump;
+        Ok(())
+    }
+
+    /// Every material goal needs its vault before the pool can take funds.
+    pub fn vaults_ready(&self) -> bool {
+        self.material_goals()
+            .iter()
+            .all(|g| g.mint != Pubkey::default())
+    }
+
+    /// Mirrors a material vault's balance onto its goal and returns the
+    /// goal's slot. Tokens of any other mint never count toward the goal.
+    pub fn sync_material_vault(
+        &mut self,
+        material_id: u8,
+        mint: Pubkey,
+        vault_balance: u64,
+    ) -> Result<usize> {
+        let slot = self.goal_index(material_id)?;
+        let goal = &mut self.material_goals[slot];
+        if goal.mint == Pubkey::default() || goal.mint != mint {
+            return Err(ErrorCode::MaterialMintMismatch.into());
+        }
+        goal.received = vault_balance;
+        self.sync_received()?;
+        Ok(slot)
+    }
+
+    fn sync_received(&mut self) -> Result<()> {
+        self.received_credits = self
+            .material_goals()
+            .iter()
+            .try_fold(0u64, |acc, g| acc.checked_add(g.received))
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// The pool is funded only once every material target is met.
+    pub fn goals_met(&self) -> bool {
+        self.material_goals().iter().all(|g| g.received >= g.target)
+    }
+
+    /// An under-goal pool past its deadline no longer accepts contributions.
+    pub fn is_expired(&self, now: i64) -> bool {
+        now > self.deadline && !self.goals_met()
+    }
+
+    /// Contributors can reclaim their share once the pool is cancelled or
+    /// expired while still funding.
+    pub fn is_refundable(&self, now: i64) -> bool {
+        match self.status {
+            ProjectStatus::Cancelled => true,
+            ProjectStatus::Funding => self.is_expired(now),
+            _ => false,
+        }
+    }
+
+    pub fn milestones(&self) -> &[Milestone] {
+        &self.milestones[..self.milestones_len as usize]
+    }
+
+    /// Replaces the milestone plan. Only allowed while the pool is a draft,
+    /// so contributors always fund a fixed builder and plan, and release
+    /// fractions must add up to the whole pool.
+    pub fn set_milestones(&mut self, builder: Pubkey, milestones: &[Milestone]) -> Result<()> {
+        if self.status != ProjectStatus::Draft {
+            return Err(ErrorCode::MilestonesLocked.into());
+        }
+        if builder == Pubkey::default()
+            || milestones.is_empty()
+            || milestones.len() > Self::MAX_MILESTONES
+        {
+            return Err(ErrorCode::InvalidMilestones.into());
+        }
+        let total_bps = milestones
+            .iter()
+            .try_fold(0u64, |acc, m| {
+                (m.release_bps > 0).then_some(acc + m.release_bps as u64)
+            })
+            .ok_or(ErrorCode::InvalidMilestones)?;
+        if total_bps != Self::BPS_DENOMINATOR {
+            return Err(ErrorCode::InvalidMilestones.into());
+        }
+
+        self.builder = builder;
+        self.milestones = [Milestone::default(); Self::MAX_MILESTONES];
+        self.milestones[..milestones.len()].copy_from_slice(milestones);
+        self.milestones_len = milestones.len() as u8;
+        Ok(())
+    }
+
+    /// First step of a handover, mirroring `GlobalConfig`. Proposing the
+    /// default key withdraws a pending proposal.
+    pub fn propose_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        if new_authority == self.authority {
+            return Err(ErrorCode::InvalidProjectAuthority.into());
+        }
+        self.pending_authority = new_authority;
+        Ok(())
+    }
+
+    /// Hands the pool to the pending authority and returns the previous one.
+    /// The PDA is seeded by id, so the pool's address does not change.
+    pub fn accept_authority(&mut self, signer: Pubkey) -> Result<Pubkey> {
+        if self.pending_authority == Pubkey::default() || self.pending_authority != signer {
+            return Err(ErrorCode::Unauthorized.into());
+        }
+        self.pending_authority = Pubkey::default();
+        Ok(std::mem::replace(&mut self.authority, signer))
+    }
+
+    /// Draft and funding pools are still open for edits to their metadata.
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, ProjectStatus::Draft | ProjectStatus::Funding)
+    }
+
+    /// Everything the pool raised: what is still in the vaults plus what has
+    /// already been paid out to the builder.
+    pub fn total_raised(&self) -> Result<u64> {
+        self.received_credits
+            .checked_add(self.released_credits)
+            .ok_or(ErrorCode::Overflow.into())
+    }
+
+    /// A contributor's share of the total raised, in basis points.
+    pub fn contribution_share_bps(&self, amount: u64) -> Result<u16> {
+        let total = self.total_raised()?;
+        if total == 0 || amount > total {
+            return Err(ErrorCode::InvalidAmount.into());
+        }
+        let bps = (amount as u128) * (Self::BPS_DENOMINATOR as u128) / (total as u128);
+        Ok(bps as u16)
+    }
+
+    /// Completion requires a milestone plan whose every tranche, each backed
+    /// by a construction receipt, has been released.
+    pub fn milestones_complete(&self) -> bool {
+        self.milestones_len > 0 && self.milestones_released == self.milestones_len
+    }
+
+    /// Marks the next milestone released and returns its index and the
+    /// tranche owed from each material vault, given their balances in goal
+    /// order. The last milestone sweeps whatever is left in every vault.
+    pub fn release_next_milestone(
+        &mut self,
+        vault_balances: &[u64],
+    ) -> Result<(u8, Vec<MaterialRelease>)> {
+        if self.status != ProjectStatus::Funded {
+            return Err(ErrorCode::ProjectNotFunded.into());
+        }
+        if vault_balances.len() != self.material_goals_len as usize {
+            return Err(ErrorCode::MaterialVaultMissing.into());
+        }
+        let index = self.milestones_released;
+        let milestone = *self
+            .milestones()
+            .get(index as usize)
+            .ok_or(ErrorCode::NoMilestoneToRelease)?;
+        let last = index + 1 == self.milestones_len;
+
+        let len = self.material_goals_len as usize;
+        let mut releases = Vec::with_capacity(len);
+        for (goal, &vault_balance) in self.material_goals[..len].iter_mut().zip(vault_balances) {
+            let amount = if last {
+                vault_balance
+            } else {
+                let raised = vault_balance
+                    .checked_add(goal.released)
+                    .ok_or(ErrorCode::Overflow)?;
+                let tranche = (raised as u128)
+                    .checked_mul(milestone.release_bps as u128)
+                    .ok_or(ErrorCode::Overflow)?
+                    / Self::BPS_DENOMINATOR as u128;
+                (tranche as u64).min(vault_balance)
+            };
+            goal.released = goal.released.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+            goal.received = vault_balance - amount;
+            self.released_credits = self
+                .released_credits
+                .checked_add(amount)
+                .ok_or(ErrorCode::Overflow)?;
+            releases.push(MaterialRelease {
+                material_id: goal.material_id,
+                mint: goal.mint,
+                amount,
+                remaining: goal.received,
+            });
+        }
+
+        self.milestones_released = index + 1;
+        self.sync_received()?;
+        Ok((index, releases))
+    }
+
+    /// Moves the pool to `to` if the lifecycle allows it and returns the
+    /// previous status.
+    pub fn transition(&mut self, to: ProjectStatus) -> Result<ProjectStatus> {
+        use ProjectStatus::*;
+        let from = self.status;
+        if to == Funding && from == Draft {
+            if !self.vaults_ready() {
+                return Err(ErrorCode::MaterialVaultMissing.into());
+            }
+            // The plan is frozen from here on, so it has to exist first
+            if self.milestones_len == 0 {
+                return Err(ErrorCode::InvalidMilestones.into());
+            }
+        }
+        let allowed = match to {
+            Funding => from == Draft,
+            Funded => from == Funding,
+            Completed => from == Funded && self.milestones_complete(),
+            Cancelled => matches!(from, Draft | Funding | Funded) && self.released_credits == 0,
+            Archived => matches!(from, Completed | Cancelled),
+            Draft => false,
+        };
+        if !allowed {
+            let err = match to {
+                Funding | Draft => ErrorCode::ProjectNotOpenable,
+                Funded => ErrorCode::ProjectNotFunding,
+                Completed => ErrorCode::ProjectNotCompletable,
+                Cancelled => ErrorCode::ProjectNotCancellable,
+                Archived => ErrorCode::ProjectNotArchivable,
+            };
+            return Err(err.into());
+        }
+        self.status = to;
+        Ok(from)
+    }
+
+    pub fn name(&self) -> String {
+        let bytes = &self.name[..self.name_len as usize];
+        String::from_utf8(bytes.to_vec()).unwrap_or_default()
+    }
+}
+
+/// Descriptive data for a `ProjectPool` that does not fit on the pool itself.
+/// The long-form description lives off-chain at `uri`, pinned by `content_hash`.
+#[account]
+pub struct ProjectMetadata {
+    pub project_pool: Pubkey,
+    pub bump: u8,
+    pub uri_len: u8,
+    pub uri: [u8; ProjectMetadata::URI_MAX_LEN],
+    pub content_hash: [u8; 32],
+    pub image_uri_len: u8,
+    pub image_uri: [u8; ProjectMetadata::URI_MAX_LEN],
+    pub category: ProjectCategory,
+    pub zone_id_len: u8,
+    pub zone_id: [u8; ProofOfCollectionReceipt::ZONE_ID_MAX_LEN],
+    /// Incremented on every edit.
+    pub version: u32,
+    pub updated_at: i64,
+}
+
+impl ProjectMetadata {
+    pub const SEED_PREFIX: &'static [u8] = b"project_meta";
+    pub const URI_MAX_LEN: usize = 200;
+    pub const SIZE: usize = 8  // discriminator
+        + 32                   // project pool
+        + 1                    // bump
+        + 1                    // uri length
+        + Self::URI_MAX_LEN    // uri bytes
+        + 32                   // content hash
+        + 1                    // image uri length
+        + Self::URI_MAX_LEN    // image uri bytes
+        + 1                    // category
+        + 1                    // zone id length
+        + ProofOfCollectionReceipt::ZONE_ID_MAX_LEN // zone id bytes
+        + 4                    // version
+        + 8;                   // updated at
+
+    pub fn update(&mut self, params: &ProjectMetadataParams, now: i64) -> Result<()> {
+        if params.uri.len() > Self::URI_MAX_LEN || params.image_uri.len() > Self::URI_MAX_LEN {
+            return Err(ErrorCode::ProjectUriTooLong.into());
+        }
+        if params.zone_id.len() > ProofOfCollectionReceipt::ZONE_ID_MAX_LEN {
+            return Err(ErrorCode::ZoneIdTooLong.into());
+        }
+
+        self.uri_len = params.uri.len() as u8;
+        self.uri = [0u8; Self::URI_MAX_LEN];
+        self.uri[..params.uri.len()].copy_from_slice(params.uri.as_bytes());
+        self.content_hash = params.content_hash;
+        self.image_uri_len = params.image_uri.len() as u8;
+        self.image_uri = [0u8; Self::URI_MAX_LEN];
+        self.image_uri[..params.image_uri.len()].copy_from_slice(params.image_uri.as_bytes());
+        self.category = params.category;
+        self.zone_id_len = params.zone_id.len() as u8;
+        self.zone_id = [0u8; ProofOfCollectionReceipt::ZONE_ID_MAX_LEN];
+        self.zone_id[..params.zone_id.len()].copy_from_slice(params.zone_id.as_bytes());
+        self.version = self.version.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    pub fn uri(&self) -> String {
+        let bytes = &self.uri[..self.uri_len as usize];
+        String::from_utf8(bytes.to_vec()).unwrap_or_default()
+    }
+
+    pub fn image_uri(&self) -> String {
+        let bytes = &self.image_uri[..self.image_uri_len as usize];
+        String::from_utf8(bytes.to_vec()).unwrap_or_default()
+    }
+
+    pub fn zone_id(&self) -> String {
+        let bytes = &self.zone_id[..self.zone_id_len as usize];
+        String::from_utf8(bytes.to_vec()).unwrap_or_default()
+    }
+}
+
+#[account]
+pub struct ProofOfCollectionReceipt {
+    pub player: Pubkey,
+    pub bump: u8,
+    pub attestation_id: [u8; 32],
+    pub photo_hash: [u8; 32],
+    pub zone_id_len: u8,
+    pub zone_id: [u8; ProofOfCollectionReceipt::ZONE_ID_MAX_LEN],
+    /// Where the collection happened; must lie inside the zone's bounds.
+    pub location: GeoPoint,
+    pub material_type: u8,
+    pub quantity: u64,
+    pub timestamp: i64,
+    /// Registered verifiers whose signatures over the receipt hash were counted.
+    pub approvals: u8,
+    pub redeemed: bool,
+    pub status: ReceiptStatus,
+    /// BLOCK can be minted once this passes without an open dispute.
+    pub challenge_ends_at: i64,
+    /// Lamports the submitter staked on this receipt.
+    pub bond: u64,
+}
+
+impl ProofOfCollectionReceipt {
+    pub const SEED_PREFIX: &'static [u8] = b"poc";
+    pub const ZONE_ID_MAX_LEN: usize = 32;
+    /// Domain separator prepended to the payload the verifier signs.
+    pub const ATTESTATION_DOMAIN: &'static [u8] = b"ecobuild:poc:v2";
+    pub const SIZE: usize = 8  // discriminator
+        + 32                   // player pubkey
+        + 1                    // bump
+        + 32                   // attestation id
+        + 32                   // photo hash
+        + 1                    // zone id length
+        + Self::ZONE_ID_MAX_LEN // zone id bytes
+        + GeoPoint::SIZE       // location
+        + 1                    // material type
+        + 8                    // quantity
+        + 8                    // timestamp
+        + 1                    // approvals
+        + 1                    // redeemed
+        + 1                    // status
+        + 8                    // challenge ends at
+        + 8;                   // bond
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        &mut self,
+        player: Pubkey,
+        bump: u8,
+        attestation_id: [u8; 32],
+        photo_hash: [u8; 32],
+        zone_id: &str,
+        location: GeoPoint,
+        material_type: u8,
+        quantity: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        if quantity == 0 {
+            return Err(ErrorCode::InvalidAmount.into());
+        }
+        if timestamp <= 0 {
+            return Err(ErrorCode::InvalidTimestamp.into());
+        }
+        if zone_id.len() > Self::ZONE_ID_MAX_LEN {
+            return Err(ErrorCode::ZoneIdTooLong.into());
+        }
+
+        self.player = player;
+        self.bump = bump;
+        self.attestation_id = attestation_id;
+        self.photo_hash = photo_hash;
+        self.zone_id_len = zone_id.len() as u8;
+        self.zone_id = [0u8; Self::ZONE_ID_MAX_LEN];
+        self.zone_id[..zone_id.len()].copy_from_slice(zone_id.as_bytes());
+        self.location = location;
+        self.material_type = material_type;
+        self.quantity = quantity;
+        self.timestamp = timestamp;
+        self.approvals = 0;
+        self.redeemed = false;
+        self.status = ReceiptStatus::Pending;
+        self.challenge_ends_at = 0;
+        self.bond = 0;
+        Ok(())
+    }
+
+    pub fn open_challenge_window(&mut self, now: i64, period: i64, bond: u64) -> Result<()> {
+        self.challenge_ends_at = now.checked_add(period).ok_or(ErrorCode::Overflow)?;
+        self.bond = bond;
+        Ok(())
+    }
+
+    pub fn open_dispute(&mut self, now: i64) -> Result<()> {
+        match self.status {
+            ReceiptStatus::Pending if now < self.challenge_ends_at && !self.redeemed => {
+                self.status = ReceiptStatus::Disputed;
+                Ok(())
+            }
+            ReceiptStatus::Pending => Err(ErrorCode::ChallengeWindowClosed.into()),
+            ReceiptStatus::Disputed => Err(ErrorCode::ReceiptDisputed.into()),
+            ReceiptStatus::Rejected => Err(ErrorCode::ReceiptRejected.into()),
+        }
+    }
+
+    /// An upheld dispute rejects the receipt for good; a dismissed one puts
+    /// it back in line for minting.
+    pub fn resolve_dispute(&mut self, upheld: bool) -> Result<()> {
+        if self.status != ReceiptStatus::Disputed {
+            return Err(ErrorCode::ReceiptNotDisputed.into());
+        }
+        self.status = if upheld {
+            ReceiptStatus::Rejected
+        } else {
+            ReceiptStatus::Pending
+        };
+        Ok(())
+    }
+
+    /// Clears the recorded bond and returns how many lamports to pay out.
+    pub fn take_bond(&mut self) -> u64 {
+        std::mem::take(&mut self.bond)
+    }
+
+    /// A receipt can only be minted or closed once it is unredeemed,
+    /// undisputed and past its challenge window.
+    fn require_settled(&self, now: i64) -> Result<()> {
+        if self.redeemed {
+            return Err(ErrorCode::ReceiptAlreadyRedeemed.into());
+        }
+        match self.status {
+            ReceiptStatus::Disputed => Err(ErrorCode::ReceiptDisputed.into()),
+            ReceiptStatus::Rejected => Err(ErrorCode::ReceiptRejected.into()),
+            ReceiptStatus::Pending if now < self.challenge_ends_at => {
+                Err(ErrorCode::ChallengeWindowOpen.into())
+            }
+            ReceiptStatus::Pending => Ok(()),
+        }
+    }
+
+    /// Marks the receipt as redeemed and returns the BLOCK amount it is worth
+    /// under the material's current conversion rate.
+    pub fn redeem(&mut self, material: &MaterialConfig, now: i64) -> Result<u64> {
+        self.require_settled(now)?;
+        material.require_usable(self.material_type)?;
+        let blocks = material.blocks_for(self.quantity)?;
+        self.redeemed = true;
+        Ok(blocks)
+    }
+
+    /// Requires a settled receipt that its material can no longer mint, e.g.
+    /// after the material was retired or its rate raised above the quantity.
+    pub fn require_unmintable(&self, material: &MaterialConfig, now: i64) -> Result<()> {
+        self.require_settled(now)?;
+        let mintable = material
+            .require_usable(self.material_type)
+            .and_then(|_| material.blocks_for(self.quantity))
+            .is_ok();
+        if mintable {
+            return Err(ErrorCode::ReceiptStillMintable.into());
+        }
+        Ok(())
+    }
+
+    /// Canonical bytes describing this receipt: domain, player, attestation
+    /// id, photo hash, length-prefixed zone id, little-endian latitude and
+    /// longitude, material type, then little-endian quantity and timestamp.
+    pub fn attestation_message(&self) -> Vec<u8> {
+        let zone_id = &self.zone_id[..self.zone_id_len as usize];
+        let mut message = Vec::with_capacity(
+            Self::ATTESTATION_DOMAIN.len()
+                + 32
+                + 32
+                + 32
+                + 1
+                + zone_id.len()
+                + GeoPoint::SIZE
+                + 1
+                + 8
+                + 8,
+        );
+        message.extend_from_slice(Self::ATTESTATION_DOMAIN);
+        message.extend_from_slice(self.player.as_ref());
+        message.extend_from_slice(&self.attestation_id);
+        message.extend_from_slice(&self.photo_hash);
+        message.push(self.zone_id_len);
+        message.extend_from_slice(zone_id);
+        message.extend_from_slice(&self.location.lat_e7.to_le_bytes());
+        message.extend_from_slice(&self.location.lon_e7.to_le_bytes());
+        message.push(self.material_type);
+        message.extend_from_slice(&self.quantity.to_le_bytes());
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message
+    }
+
+    /// SHA-256 of the attestation message; this is what verifiers sign.
+    pub fn attestation_hash(&self) -> [u8; 32] {
+        hash(&self.attestation_message()).to_bytes()
+    }
+
+    pub fn derive_pda(program_id: &Pubkey, player: &Pubkey, attestation_id: &[u8; 32]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[Self::SEED_PREFIX, player.as_ref(), attestation_id],
+            program_id,
+        )
+    }
+
+    pub fn zone_id(&self) -> String {
+        let bytes = &self.zone_id[..self.zone_id_len as usize];
+        String::from_utf8(bytes.to_vec()).unwrap_or_default()
+    }
+}
+
+/// Records the receipt a photo hash was first used for, so the same photo
+/// cannot back a second receipt.
+#[account]
+pub struct PhotoHashClaim {
+    pub photo_hash: [u8; 32],
+    pub receipt: Pubkey,
+    pub player: Pubkey,
+    pub bump: u8,
+    pub claimed_at: i64,
+}
+
+impl PhotoHashClaim {
+    pub const SEED_PREFIX: &'static [u8] = b"photo";
+    pub const SIZE: usize = 8  // discriminator
+        + 32                   // photo hash
+        + 32                   // receipt
+        + 32                   // player
+        + 1                    // bump
+        + 8;                   // claimed at
+
+    /// Claims a fresh account; an existing claim means the photo is taken.
+    pub fn claim(
+        &mut self,
+        photo_hash: [u8; 32],
+        receipt: Pubkey,
+        player: Pubkey,
+        bump: u8,
+        now: i64,
+    ) -> Result<()> {
+        if self.receipt != Pubkey::default() {
+            return Err(ErrorCode::PhotoHashAlreadyClaimed.into());
+        }
+        self.photo_hash = photo_hash;
+        self.receipt = receipt;
+        self.player = player;
+        self.bump = bump;
+        self.claimed_at = now;
+        Ok(())
+    }
+}
+
+/// A challenge against a receipt, holding the challenger's bond until the
+/// arbiter resolves it.
+#[account]
+pub struct Dispute {
+    pub receipt: Pubkey,
+    pub challenger: Pubkey,
+    pub bump: u8,
+    pub bond: u64,
+    /// Hash of the off-chain evidence, e.g. the earlier photo it duplicates.
+    pub reason_hash: [u8; 32],
+    pub opened_at: i64,
+}
+
+impl Dispute {
+    pub const SEED_PREFIX: &'static [u8] = b"dispute";
+    pub const SIZE: usize = 8  // discriminator
+        + 32                   // receipt
+        + 32                   // challenger
+        + 1                    // bump
+        + 8                    // bond
+        + 32                   // reason hash
+        + 8;                   // opened at
+}
+
+/// Verifiers allowed to approve proof-of-collection receipts, and how many
+/// of them must sign each one.
+#[account]
+pub struct VerifierRegistry {
+    pub bump: u8,
+    pub threshold: u8,
+    pub verifiers_len: u8,
+    pub verifiers: [Pubkey; VerifierRegistry::MAX_VERIFIERS],
+}
+
+impl VerifierRegistry {
+    pub const SEED_PREFIX: &'static [u8] = b"verifier_registry";
+    pub const MAX_VERIFIERS: usize = 10;
+    pub const SIZE: usize = 8  // discriminator
+        + 1                    // bump
+        + 1                    // threshold
+        + 1                    // verifiers length
+        + Self::MAX_VERIFIERS * 32; // verifiers
+
+    pub fn initialize(&mut self, bump: u8, verifiers: &[Pubkey], threshold: u8) -> Result<()> {
+        if verifiers.len() > Self::MAX_VERIFIERS {
+            return Err(ErrorCode::InvalidVerifierSet.into());
+        }
+        self.bump = bump;
+        self.verifiers_len = 0;
+        self.verifiers = [Pubkey::default(); Self::MAX_VERIFIERS];
+        for verifier in verifiers {
+            self.add_verifier(*verifier)?;
+        }
+        self.set_threshold(threshold)
+    }
+
+    pub fn verifiers(&self) -> &[Pubkey] {
+        &self.verifiers[..self.verifiers_len as usize]
+    }
+
+    pub fn is_registered(&self, verifier: &Pubkey) -> bool {
+        self.verifiers().contains(verifier)
+    }
+
+    pub fn add_verifier(&mut self, verifier: Pubkey) -> Result<()> {
+        if verifier == Pubkey::default() || self.verifiers_len as usize >= Self::MAX_VERIFIERS {
+            return Err(ErrorCode::InvalidVerifierSet.into());
+        }
+        if self.is_registered(&verifier) {
+            return Err(ErrorCode::VerifierAlreadyRegistered.into());
+        }
+        self.verifiers[self.verifiers_len as usize] = verifier;
+        self.verifiers_len += 1;
+        Ok(())
+    }
+
+    /// Removing a verifier may not leave the threshold unreachable.
+    pub fn remove_verifier(&mut self, verifier: Pubkey) -> Result<()> {
+        let index = self
+            .verifiers()
+            .iter()
+            .position(|v| *v == verifier)
+            .ok_or(ErrorCode::VerifierNotRegistered)?;
+        if self.verifiers_len - 1 < self.threshold {
+            return Err(ErrorCode::InvalidVerifierSet.into());
+        }
+        let last = self.verifiers_len as usize - 1;
+        self.verifiers[index] = self.verifiers[last];
+        self.verifiers[last] = Pubkey::default();
+        self.verifiers_len -= 1;
+        Ok(())
+    }
+
+    /// Swaps a key in place, e.g. after a verifier's key is compromised.
+    pub fn rotate_verifier(&mut self, old_verifier: Pubkey, new_verifier: Pubkey) -> Result<()> {
+        if new_verifier == Pubkey::default() {
+            return Err(ErrorCode::InvalidVerifierSet.into());
+        }
+        if self.is_registered(&new_verifier) {
+            return Err(ErrorCode::VerifierAlreadyRegistered.into());
+        }
+        let index = self
+            .verifiers()
+            .iter()
+            .position(|v| *v == old_verifier)
+            .ok_or(ErrorCode::VerifierNotRegistered)?;
+        self.verifiers[index] = new_verifier;
+        Ok(())
+    }
+
+    pub fn set_threshold(&mut self, threshold: u8) -> Result<()> {
+        if threshold == 0 || threshold > self.verifiers_len {
+            return Err(ErrorCode::InvalidVerifierSet.into());
+        }
+        self.threshold = threshold;
+        Ok(())
+    }
+
+    /// Counts the distinct registered verifiers among `signers` and requires
+    /// at least `threshold` of them.
+    pub fn require_quorum(&self, signers: &[Pubkey]) -> Result<u8> {
+        let mut approvals = 0u8;
+        for (i, signer) in signers.iter().enumerate() {
+            if self.is_registered(signer) && !signers[..i].contains(signer) {
+                approvals += 1;
+            }
+        }
+        if approvals < self.threshold {
+            return Err(ErrorCode::VerifierQuorumNotMet.into());
+        }
+        Ok(approvals)
+    }
+}
+
+#[account]
+pub struct ConstructionReceipt {
+    pub project_pool: Pubkey,
+    pub milestone_index: u8,
+    pub bump: u8,
+    /// Description hash of the milestone this receipt proves, as locked in
+    /// the pool's plan.
+    pub description_hash: [u8; 32],
+    pub photo_hash: [u8; 32],
+    pub zone_id_len: u8,
+    pub zone_id: [u8; ProofOfCollectionReceipt::ZONE_ID_MAX_LEN],
+    pub timestamp: i64,
+    pub verifier: Pubkey,
+    pub signature: [u8; 64],
+}
+
+impl ConstructionReceipt {
+    pub const SEED_PREFIX: &'static [u8] = b"construction";
+    /// Domain separator prepended to the payload the verifier signs.
+    pub const ATTESTATION_DOMAIN: &'static [u8] = b"ecobuild:construction:v2";
+    pub const SIZE: usize = 8  // discriminator
+        + 32                   // project pool
+        + 1                    // milestone index
+        + 1                    // bump
+        + 32                   // description hash
+        + 32                   // photo hash
+        + 1                    // zone id length
+        + ProofOfCollectionReceipt::ZONE_ID_MAX_LEN // zone id bytes
+        + 8                    // timestamp
+        + 32                   // verifier pubkey
+        + 64;                  // verifier signature
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        &mut self,
+        project_pool: Pubkey,
+        milestone_index: u8,
+        bump: u8,
+        description_hash: [u8; 32],
+        photo_hash: [u8; 32],
+        zone_id: &str,
+        timestamp: i64,
+    ) -> Result<()> {
+        if timestamp <= 0 {
+            return Err(ErrorCode::InvalidTimestamp.into());
+        }
+        if zone_id.len() > ProofOfCollectionReceipt::ZONE_ID_MAX_LEN {
+            return Err(ErrorCode::ZoneIdTooLong.into());
+        }
+
+        self.project_pool = project_pool;
+        self.milestone_index = milestone_index;
+        self.bump = bump;
+        self.description_hash = description_hash;
+        self.photo_hash = photo_hash;
+        self.zone_id_len = zone_id.len() as u8;
+        self.zone_id = [0u8; ProofOfCollectionReceipt::ZONE_ID_MAX_LEN];
+        self.zone_id[..zone_id.len()].copy_from_slice(zone_id.as_bytes());
+        self.timestamp = timestamp;
+        self.verifier = Pubkey::default();
+        self.signature = [0u8; 64];
+        Ok(())
+    }
+
+    /// Canonical bytes the verifier signs: domain, project pool, milestone
+    /// index, milestone description hash, photo hash, length-prefixed zone
+    /// id, little-endian timestamp.
+    pub fn attestation_message(&self) -> Vec<u8> {
+        let zone_id = &self.zone_id[..self.zone_id_len as usize];
+        let mut message = Vec::with_capacity(
+            Self::ATTESTATION_DOMAIN.len() + 32 + 1 + 32 + 32 + 1 + zone_id.len() + 8,
+        );
+        message.extend_from_slice(Self::ATTESTATION_DOMAIN);
+        message.extend_from_slice(self.project_pool.as_ref());
+        message.push(self.milestone_index);
+        message.extend_from_slice(&self.description_hash);
+        message.extend_from_slice(&self.photo_hash);
+        message.push(self.zone_id_len);
+        message.extend_from_slice(zone_id);
+        message.extend_from_slice(&self.timestamp.to_le_bytes());
+        message
+    }
+}
+
+#[account]
+pub struct MaterialConfig {
+    pub material_id: u8,
+    pub bump: u8,
+    pub active: bool,
+    /// Collected units of this material needed for one BLOCK.
+    pub units_per_block: u64,
+    /// Optional SPL mint dedicated to this material.
+    pub mint: Option<Pubkey>,
+    pub name_len: u8,
+    pub name: [u8; MaterialConfig::NAME_MAX_LEN],
+}
+
+impl MaterialConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"material";
+    pub const NAME_MAX_LEN: usize = 32;
+    pub const SIZE: usize = 8  // discriminator
+        + 1                    // material id
+        + 1                    // bump
+        + 1                    // active
+        + 8                    // units per block
+        + 1 + 32               // optional mint
+        + 1                    // name length
+        + Self::NAME_MAX_LEN;  // name bytes
+
+    pub fn initialize(
+        &mut self,
+        material_id: u8,
+        bump: u8,
+        name: &str,
+        units_per_block: u64,
+        mint: Option<Pubkey>,
+    ) -> Result<()> {
+        if name.len() > Self::NAME_MAX_LEN {
+            return Err(ErrorCode::NameTooLong.into());
+        }
+
+        self.material_id = material_id;
+        self.bump = bump;
+        self.name_len = name.len() as u8;
+        self.name = [0u8; Self::NAME_MAX_LEN];
+        self.name[..name.len()].copy_from_slice(name.as_bytes());
+        self.update(true, units_per_block, mint)
+    }
+
+    pub fn update(&mut self, active: bool, units_per_block: u64, mint: Option<Pubkey>) -> Result<()> {
+        if units_per_block == 0 {
+            return Err(ErrorCode::InvalidAmount.into());
+        }
+        self.active = active;
+        self.units_per_block = units_per_block;
+        self.mint = mint;
+        Ok(())
+    }
+
+    /// Requires this registry entry to be the active config for `material_id`.
+    pub fn require_usable(&self, material_id: u8) -> Result<()> {
+        if self.material_id != material_id {
+            return Err(ErrorCode::InvalidMaterialType.into());
+        }
+        if !self.active {
+            return Err(ErrorCode::MaterialInactive.into());
+        }
+        Ok(())
+    }
+
+    /// BLOCK amount for a collected quantity, rounded down.
+    pub fn blocks_for(&self, quantity: u64) -> Result<u64> {
+        let blocks = quantity / self.units_per_block;
+        if blocks == 0 {
+            return Err(ErrorCode::InvalidAmount.into());
+        }
+        Ok(blocks)
+    }
+
+    pub fn name(&self) -> String {
+        let bytes = &self.name[..self.name_len as usize];
+        String::from_utf8(bytes.to_vec()).unwrap_or_default()
+    }
+}
+
+/// Admin-registered collection area. Receipts must name an active zone and be
+/// located inside its bounds; the zone keeps running per-material totals of
+/// minted receipts for the dashboard heat map.
+#[account]
+pub struct Zone {
+    pub bump: u8,
+    pub active: bool,
+    pub zone_id_len: u8,
+    pub zone_id: [u8; ProofOfCollectionReceipt::ZONE_ID_MAX_LEN],
+    pub name_len: u8,
+    pub name: [u8; Zone::NAME_MAX_LEN],
+    pub bounds: ZoneBounds,
+    pub receipts_count: u64,
+    /// Collected quantity per material, indexed by material id. Only
+    /// receipts that were minted are counted.
+    pub material_totals: [u64; Zone::MAX_MATERIALS],
+}
+
+impl Zone {
+    pub const SEED_PREFIX: &'static [u8] = b"zone";
+    pub const NAME_MAX_LEN: usize = 32;
+    /// One slot for every material id the registry can hold.
+    pub const MAX_MATERIALS: usize = u8::MAX as usize + 1;
+    pub const SIZE: usize = 8  // discriminator
+        + 1                    // bump
+        + 1                    // active
+        + 1                    // zone id length
+        + ProofOfCollectionReceipt::ZONE_ID_MAX_LEN // zone id bytes
+        + 1                    // name length
+        + Self::NAME_MAX_LEN   // name bytes
+        + ZoneBounds::SIZE     // bounding box
+        + 8                    // receipts count
+        + Self::MAX_MATERIALS * 8; // material totals
+
+    pub fn initialize(&mut self, bump: u8, zone_id: &str, name: &str, bounds: ZoneBounds) -> Result<()> {
+        if zone_id.is_empty() {
+            return Err(ErrorCode::InvalidZoneId.into());
+        }
+        if zone_id.len() > ProofOfCollectionReceipt::ZONE_ID_MAX_LEN {
+            return Err(ErrorCode::ZoneIdTooLong.into());
+        }
+        if name.len() > Self::NAME_MAX_LEN {
+            return Err(ErrorCode::NameTooLong.into());
+        }
+
+        self.bump = bump;
+        self.zone_id_len = zone_id.len() as u8;
+        self.zone_id = [0u8; ProofOfCollectionReceipt::ZONE_ID_MAX_LEN];
+        self.zone_id[..zone_id.len()].copy_from_slice(zone_id.as_bytes());
+        self.name_len = name.len() as u8;
+        self.name = [0u8; Self::NAME_MAX_LEN];
+        self.name[..name.len()].copy_from_slice(name.as_bytes());
+        self.receipts_count = 0;
+        self.material_totals = [0u64; Self::MAX_MATERIALS];
+        self.update(true, bounds)
+    }
+
+    pub fn update(&mut self, active: bool, bounds: ZoneBounds) -> Result<()> {
+        bounds.validate()?;
+        self.active = active;
+        self.bounds = bounds;
+        Ok(())
+    }
+
+    pub fn record_collection(&mut self, material_id: u8, quantity: u64) -> Result<()> {
+        let total = &mut self.material_totals[material_id as usize];
+        *total = total.checked_add(quantity).ok_or(ErrorCode::Overflow)?;
+        self.receipts_count = self
+            .receipts_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    pub fn zone_id(&self) -> String {
+        let bytes = &self.zone_id[..self.zone_id_len as usize];
+        String::from_utf8(bytes.to_vec()).unwrap_or_default()
+    }
+
+    pub fn name(&self) -> String {
+        let bytes = &self.name[..self.name_len as usize];
+        String::from_utf8(bytes.to_vec()).unwrap_or_default()
+    }
+}
+
+#[account]
+pub struct Contribution {
+    pub project_pool: Pubkey,
+    pub contributor: Pubkey,
+    pub bump: u8,
+    /// Running total contributed by this player to this pool.
+    pub amount: u64,
+    /// Contributed per material, indexed like the pool's material goals.
+    pub amounts: [u64; ProjectPool::MAX_MATERIAL_GOALS],
+    /// Set once every material has been refunded.
+    pub refunded: bool,
+    pub first_contributed_at: i64,
+    pub last_contributed_at: i64,
+    pub count: u32,
+    pub badge_claimed: bool,
+}
+
+impl Contribution {
+    pub const SEED_PREFIX: &'static [u8] = b"contribution";
+    pub const SIZE: usize = 8  // discriminator
+        + 32                   // project pool
+        + 32                   // contributor
+        + 1                    // bump
+        + 8                    // amount
+        + ProjectPool::MAX_MATERIAL_GOALS * 8 // per-material amounts
+        + 1                    // refunded
+        + 8                    // first contributed at
+        + 8                    // last contributed at
+        + 4                    // count
+        + 1;                   // badge claimed
+
+    pub fn initialize(&mut self, project_pool: Pubkey, contributor: Pubkey, bump: u8) {
+        self.project_pool = project_pool;
+        self.contributor = contributor;
+        self.bump = bump;
+        self.amount = 0;
+        self.amounts = [0u64; ProjectPool::MAX_MATERIAL_GOALS];
+        self.refunded = false;
+        self.first_contributed_at = 0;
+        self.last_contributed_at = 0;
+        self.count = 0;
+        self.badge_claimed = false;
+    }
+
+    pub fn record(&mut self, slot: usize, amount: u64, now: i64) -> Result<()> {
+        self.amount = self
+            .amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        self.amounts[slot] = self.amounts[slot]
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        self.count = self.count.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        if self.first_contributed_at == 0 {
+            self.first_contributed_at = now;
+        }
+        self.last_contributed_at = now;
+        Ok(())
+    }
+
+    /// Clears one material's share and returns the amount owed from its
+    /// vault. The contribution counts as refunded once every share is back.
+    pub fn refund(&mut self, slot: usize) -> Result<u64> {
+        let amount = std::mem::take(&mut self.amounts[slot]);
+        if self.refunded || amount == 0 {
+            return Err(ErrorCode::NothingToRefund.into());
+        }
+        self.refunded = self.amounts.iter().all(|&a| a == 0);
+        Ok(amount)
+    }
+
+    /// Marks the completion badge claimed; each contributor gets one.
+    pub fn claim_badge(&mut self) -> Result<()> {
+        if self.refunded || self.amount == 0 {
+            return Err(ErrorCode::NotAContributor.into());
+        }
+        if self.badge_claimed {
+            return Err(ErrorCode::BadgeAlreadyClaimed.into());
+        }
+        self.badge_claimed = true;
+        Ok(())
+    }
+}
+
+/// One-of-one Token-2022 NFT minted to each contributor of a completed pool.
+/// The mint is a PDA of the contribution and carries its metadata inline:
+/// the pool name, the project's `ProjectMetadata` uri, and the contributor's
+/// amount and share as additional fields.
+pub struct CompletionBadge;
+
+impl CompletionBadge {
+    pub const SEED_PREFIX: &'static [u8] = b"badge";
+    pub const NAME_PREFIX: &'static str = "EcoBuild: ";
+    pub const SYMBOL: &'static str = "ECOBADGE";
+    pub const FALLBACK_URI_PREFIX: &'static str = "ecobuild://projects/";
+
+    pub fn name(project_name: &str) -> String {
+        format!("{}{}", Self::NAME_PREFIX, project_name)
+    }
+
+    /// The project's metadata uri, or one derived from the pool name when the
+    /// pool never published metadata.
+    pub fn uri(metadata: Option<&ProjectMetadata>, project_name: &str) -> String {
+        match metadata.map(ProjectMetadata::uri) {
+            Some(uri) if !uri.is_empty() => uri,
+            _ => Self::fallback_uri(project_name),
+        }
+    }
+
+    /// Lowercase slug of the pool name, e.g. `ecobuild://projects/park-bench`.
+    pub fn fallback_uri(project_name: &str) -> String {
+        let slug: String = project_name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+            .collect();
+        let slug: Vec<&str> = slug.split('-').filter(|part| !part.is_empty()).collect();
+        format!("{}{}", Self::FALLBACK_URI_PREFIX, slug.join("-"))
+    }
+
+    /// Token-2022 metadata for a contributor's badge; the additional fields
+    /// record whose share of which pool it is.
+    pub fn metadata(
+        project_pool: Pubkey,
+        mint: Pubkey,
+        project_name: &str,
+        uri: String,
+        contributed: u64,
+        share_bps: u16,
+    ) -> Result<TokenMetadata> {
+        Ok(TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(project_pool))?,
+            mint,
+            name: Self::name(project_name),
+            symbol: Self::SYMBOL.to_string(),
+            uri,
+            additional_metadata: vec![
+                ("project".to_string(), project_pool.to_string()),
+                ("contributed".to_string(), contributed.to_string()),
+                ("share_bps".to_string(), share_bps.to_string()),
+            ],
+        })
+    }
+}
+
+/// Per-material target and vault stored on a `ProjectPool`. Each material
+/// is collected in its own vault, holding only that material's mint.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MaterialGoal {
+    pub material_id: u8,
+    pub target: u64,
+    /// Mirrors the material vault's balance.
+    pub received: u64,
+    /// Mint held by the vault; unset until the vault is created.
+    pub mint: Pubkey,
+    pub vault_bump: u8,
+    /// Paid out of the vault to the builder so far.
+    pub released: u64,
+}
+
+impl MaterialGoal {
+    pub const SIZE: usize = 1 + 8 + 8 + 32 + 1 + 8;
+}
+
+/// A tranche of the pool released to the builder once the work it
+/// describes is done.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Milestone {
+    /// Hash of the off-chain milestone description.
+    pub description_hash: [u8; 32],
+    /// Share of the pool released, in basis points.
+    pub release_bps: u16,
+}
+
+impl Milestone {
+    pub const SIZE: usize = 32 + 2;
+}
+
+/// One material's share of a milestone payout, in that material's mint.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaterialRelease {
+    pub material_id: u8,
+    pub mint: Pubkey,
+    /// Paid to the builder by this milestone.
+    pub amount: u64,
+    /// Left in the material's vault afterwards.
+    pub remaining: u64,
+}
+
+// ── Instruction arguments ─────────────────────────────────────────────
+
+/// Material quantity a project pool needs, declared at creation.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaterialTarget {
+    pub material_id: u8,
+    pub target: u64,
+}
+
+/// Tunable `GlobalConfig` parameters; `None` leaves a field unchanged.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ConfigParams {
+    pub verifier: Option<Pubkey>,
+    pub curator: Option<Pubkey>,
+    pub pauser: Option<Pubkey>,
+    pub arbiter: Option<Pubkey>,
+    pub blocks_per_brick: Option<u64>,
+    pub challenge_period: Option<i64>,
+    pub dispute_bond: Option<u64>,
+    pub max_receipt_age: Option<i64>,
+    pub max_future_skew: Option<i64>,
+}
+
+/// Zone geofence as a lat/long bounding box in fixed-point degrees
+/// scaled by 1e7, matching common GPS precision.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ZoneBounds {
+    pub min_lat_e7: i32,
+    pub min_lon_e7: i32,
+    pub max_lat_e7: i32,
+    pub max_lon_e7: i32,
+}
+
+impl ZoneBounds {
+    pub const SIZE: usize = 4 * 4;
+    pub const MAX_LAT_E7: i32 = 90 * 10_000_000;
+    pub const MAX_LON_E7: i32 = 180 * 10_000_000;
+
+    pub fn validate(&self) -> Result<()> {
+        let lat_ok = -Self::MAX_LAT_E7 <= self.min_lat_e7
+            && self.min_lat_e7 <= self.max_lat_e7
+            && self.max_lat_e7 <= Self::MAX_LAT_E7;
+        let lon_ok = -Self::MAX_LON_E7 <= self.min_lon_e7
+            && self.min_lon_e7 <= self.max_lon_e7
+            && self.max_lon_e7 <= Self::MAX_LON_E7;
+        if !lat_ok || !lon_ok {
+            return Err(ErrorCode::InvalidZoneBounds.into());
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, lat_e7: i32, lon_e7: i32) -> bool {
+        (self.min_lat_e7..=self.max_lat_e7).contains(&lat_e7)
+            && (self.min_lon_e7..=self.max_lon_e7).contains(&lon_e7)
+    }
+}
+
+/// A GPS fix in fixed-point degrees scaled by 1e7, as in `ZoneBounds`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GeoPoint {
+    pub lat_e7: i32,
+    pub lon_e7: i32,
+}
+
+impl GeoPoint {
+    pub const SIZE: usize = 4 + 4;
+}
+
+/// Replacement contents for a `ProjectMetadata` account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ProjectMetadataParams {
+    pub uri: String,
+    pub content_hash: [u8; 32],
+    pub image_uri: String,
+    pub category: ProjectCategory,
+    pub zone_id: String,
+}
+
+// ── Enums ─────────────────────────────────────────────────────────────
+
+/// Project pool lifecycle:
+/// Draft -> Funding -> Funded -> Completed -> Archived, with Cancelled
+/// reachable before completion and archivable afterwards.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectStatus {
+    Draft,
+    Funding,
+    Funded,
+    Completed,
+    Cancelled,
+    Archived,
+}
+
+/// Proof-of-collection receipt lifecycle: Pending receipts become mintable
+/// once their challenge window closes; a dispute moves them to Disputed until
+/// the arbiter rejects them or returns them to Pending.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReceiptStatus {
+    #[default]
+    Pending,
+    Disputed,
+    Rejected,
+}
+
+/// What a project builds; used by clients to group and filter pools.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProjectCategory {
+    #[default]
+    Other,
+    Housing,
+    School,
+    Health,
+    Community,
+    Infrastructure,
+}
+
+// ── Signature verification ────────────────────────────────────────────
+
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+
+/// One signature checked by an Ed25519 program instruction.
+#[derive(Debug)]
+pub struct Ed25519Entry<'a> {
+    pub pubkey: Pubkey,
+    pub signature: [u8; 64],
+    pub message: &'a [u8],
+}
+
+/// Parses an Ed25519 program instruction and returns each signature it
+/// verifies. Only self-contained instructions are accepted, i.e. every
+/// offset must point into this instruction's own data (index `u16::MAX`).
+pub fn parse_ed25519_instruction(data: &[u8]) -> Result<Vec<Ed25519Entry<'_>>> {
+    let num_signatures = *data
+        .first()
+        .ok_or(ErrorCode::MalformedSignatureInstruction)? as usize;
+    let mut entries = Vec::with_capacity(num_signatures);
+
+    for i in 0..num_signatures {
+        let start = ED25519_OFFSETS_START + i * ED25519_OFFSETS_LEN;
+        let offsets = data
+            .get(start..start + ED25519_OFFSETS_LEN)
+            .ok_or(ErrorCode::MalformedSignatureInstruction)?;
+        let read = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+        let signature_offset = read(0) as usize;
+        let signature_ix = read(2);
+        let pubkey_offset = read(4) as usize;
+        let pubkey_ix = read(6);
+        let message_offset = read(8) as usize;
+        let message_size = read(10) as usize;
+        let message_ix = read(12);
+        if signature_ix != u16::MAX || pubkey_ix != u16::MAX || message_ix != u16::MAX {
+            return Err(ErrorCode::MalformedSignatureInstruction.into());
+        }
+
+        let pubkey = data
+            .get(pubkey_offset..pubkey_offset + 32)
+            .ok_or(ErrorCode::MalformedSignatureInstruction)?;
+        let message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(ErrorCode::MalformedSignatureInstruction)?;
+        let signature = data
+            .get(signature_offset..signature_offset + 64)
+            .ok_or(ErrorCode::MalformedSignatureInstruction)?;
+        entries.push(Ed25519Entry {
+            pubkey: Pubkey::try_from(pubkey)
+                .map_err(|_| ErrorCode::MalformedSignatureInstruction)?,
+            signature: signature
+                .try_into()
+                .map_err(|_| ErrorCode::MalformedSignatureInstruction)?,
+            message,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Requires that an Ed25519 program instruction earlier in the current
+/// transaction verified `signer`'s signature over exactly `message`, and
+/// returns that signature.
+pub fn require_ed25519_signature(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<[u8; 64]> {
+    let current = load_current_index_checked(instructions)?;
+    for index in 0..current {
+        let ix = load_instruction_at_checked(index as usize, instructions)?;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+        let verified = parse_ed25519_instruction(&ix.data)?
+            .into_iter()
+            .find(|entry| entry.pubkey == *signer && entry.message == message);
+        if let Some(entry) = verified {
+            return Ok(entry.signature);
+        }
+    }
+    Err(ErrorCode::MissingVerifierSignature.into())
+}
+
+/// Returns every distinct key whose signature over exactly `message` was
+/// verified by an Ed25519 program instruction earlier in the transaction.
+pub fn collect_ed25519_signers(instructions: &AccountInfo, message: &[u8]) -> Result<Vec<Pubkey>> {
+    let mut signers: Vec<Pubkey> = Vec::new();
+    let current = load_current_index_checked(instructions)?;
+    for index in 0..current {
+        let ix = load_instruction_at_checked(index as usize, instructions)?;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+        for entry in parse_ed25519_instruction(&ix.data)? {
+            if entry.message == message && !signers.contains(&entry.pubkey) {
+                signers.push(entry.pubkey);
+            }
+        }
+    }
+    Ok(signers)
+}
+
+/// Moves lamports out of an account this program owns.
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let remaining = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    **from.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    Ok(())
+}
+
+fn emit_registry_updated(registry: &VerifierRegistry) -> Result<()> {
+    emit!(VerifierRegistryUpdated {
+        verifiers: registry.verifiers().to_vec(),
+        threshold: registry.threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+// ── Events ────────────────────────────────────────────────────────────
+
+#[event]
+pub struct ReceiptDisputed {
+    pub receipt: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub reason_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub receipt: Pubkey,
+    pub challenger: Pubkey,
+    pub arbiter: Pubkey,
+    pub upheld: bool,
+    /// Bond paid to the winning side.
+    pub payout: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReceiptClosed {
+    pub receipt: Pubkey,
+    pub player: Pubkey,
+    /// Submitter's bond returned along with the account's rent.
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierRegistryUpdated {
+    pub verifiers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub authority: Pubkey,
+    pub verifier: Pubkey,
+    pub curator: Pubkey,
+    pub pauser: Pubkey,
+    pub arbiter: Pubkey,
+    pub blocks_per_brick: u64,
+    pub challenge_period: i64,
+    pub dispute_bond: u64,
+    pub max_receipt_age: i64,
+    pub max_future_skew: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaterialCreated {
+    pub material_id: u8,
+    pub name: String,
+    pub units_per_block: u64,
+    pub mint: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaterialUpdated {
+    pub material_id: u8,
+    pub active: bool,
+    pub units_per_block: u64,
+    pub mint: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ZoneCreated {
+    pub zone_id: String,
+    pub name: String,
+    pub bounds: ZoneBounds,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ZoneUpdated {
+    pub zone_id: String,
+    pub active: bool,
+    pub bounds: ZoneBounds,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramPaused {
+    pub pauser: Pubkey,
+    pub flags: u8,
+    pub paused: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramUnpaused {
+    pub pauser: Pubkey,
+    pub flags: u8,
+    pub paused: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BlocksMinted {
+    pub player: Pubkey,
+    pub receipt: Pubkey,
+    pub amount: u64,
+    pub waste_type: u8,
+    pub units_per_block: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreditsContributed {
+    pub player: Pubkey,
+    pub project_pool: Pubkey,
+    pub mint: Pubkey,
+    pub material_id: u8,
+    pub amount: u64,
+    pub vault_balance: u64,
+    pub contributor_total: u64,
+    pub contribution_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaterialVaultCreated {
+    pub project_pool: Pubkey,
+    pub material_id: u8,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub player: Pubkey,
+    pub project_pool: Pubkey,
+    pub material_id: u8,
+    pub amount: u64,
+    pub vault_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProjectMilestonesSet {
+    pub project_pool: Pubkey,
+    pub builder: Pubkey,
+    pub milestones: Vec<Milestone>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConstructionReceiptSubmitted {
+    pub project_pool: Pubkey,
+    pub milestone_index: u8,
+    pub description_hash: [u8; 32],
+    pub photo_hash: [u8; 32],
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CompletionBadgeClaimed {
+    pub player: Pubkey,
+    pub project_pool: Pubkey,
+    pub badge_mint: Pubkey,
+    pub contributed: u64,
+    pub share_bps: u16,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct ConvertToBrick<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [GlobalConfig::SEED_PREFIX],
-        bump = global_config.bump
-    )]
-    pub global_config: Account<'info, GlobalConfig>,
-    #[account(
-        mut,
-        seeds = [b"block_mint"],
-        bump,
-        constraint = block_mint.key() == global_config.block_mint
-    )]
-    pub block_mint: Account<'info, Mint>,
-    #[account(
-        mut,
-        seeds = [PlayerProfile::SEED_PREFIX, authority.key().as_ref()],
-        bump = player_profile.bump,
-        constraint = player_profile.authority == authority.key()
-    )]
-    pub player_profile: Account<'info, PlayerProfile>,
-    #[account(
-        mut,
-        associated_token::mint = block_mint,
-        associated_token::authority = authority,
-    )]
-    pub player_token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct ProjectCreated {
+    pub project_pool: Pubkey,
+    pub id: u64,
+    pub authority: Pubkey,
+    pub goal_credits: u64,
+    pub deadline: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProjectAuthorityProposed {
+    pub project_pool: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProjectAuthorityTransferred {
+    pub project_pool: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProjectMetadataUpdated {
+    pub project_pool: Pubkey,
+    pub authority: Pubkey,
+    pub uri: String,
+    pub content_hash: [u8; 32],
+    pub image_uri: String,
+    pub category: ProjectCategory,
+    pub zone_id: String,
+    pub version: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneReleased {
+    pub project_pool: Pubkey,
+    pub builder: Pubkey,
+    pub released_by: Pubkey,
+    pub milestone_index: u8,
+    /// Paid and remaining amounts per material, in the pool's goal order.
+    pub releases: Vec<MaterialRelease>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProjectStatusChanged {
+    pub project_pool: Pubkey,
+    pub from: ProjectStatus,
+    pub to: ProjectStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BrickConverted {
+    pub player: Pubkey,
+    pub count: u64,
+    pub new_brick_count: u64,
+    pub blocks_burned: u64,
+    pub blocks_per_brick: u64,
+    pub timestamp: i64,
+}
+
+// ── Errors ────────────────────────────────────────────────────────────
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Project name exceeds max length")]
+    NameTooLong,
+    #[msg("Zone id exceeds max length")]
+    ZoneIdTooLong,
+    #[msg("Material type is invalid")]
+    InvalidMaterialType,
+    #[msg("Timestamp is invalid")]
+    InvalidTimestamp,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Unauthorized signer")]
+    Unauthorized,
+    #[msg("Insufficient BLOCK tokens for a brick")]
+    InsufficientBlocks,
+    #[msg("Verifier signature over the receipt payload is missing")]
+    MissingVerifierSignature,
+    #[msg("Ed25519 signature instruction is malformed")]
+    MalformedSignatureInstruction,
+    #[msg("Receipt has already been redeemed for BLOCK")]
+    ReceiptAlreadyRedeemed,
+    #[msg("Insufficient token balance to contribute")]
+    InsufficientBalance,
+    #[msg("Config parameter is invalid")]
+    InvalidConfigParam,
+    #[msg("Operation is paused")]
+    Paused,
+    #[msg("Pause flags are invalid")]
+    InvalidPauseFlags,
+    #[msg("Material is not active")]
+    MaterialInactive,
+    #[msg("Project is not accepting contributions")]
+    ProjectNotFunding,
+    #[msg("Project can only be opened from draft")]
+    ProjectNotOpenable,
+    #[msg("Project can only be completed once funded and every milestone is released")]
+    ProjectNotCompletable,
+    #[msg("Project can no longer be cancelled")]
+    ProjectNotCancellable,
+    #[msg("Project can only be archived once completed or cancelled")]
+    ProjectNotArchivable,
+    #[msg("Project deadline has passed")]
+    ProjectExpired,
+    #[msg("Project is not cancelled or expired")]
+    ProjectNotRefundable,
+    #[msg("Nothing left to refund for this contribution")]
+    NothingToRefund,
+    #[msg("Material goals must be non-empty, unique and within the limit")]
+    InvalidMaterialGoals,
+    #[msg("Material is not a goal of this project")]
+    MaterialNotInProject,
+    #[msg("Milestones need a builder and release fractions summing to 100%")]
+    InvalidMilestones,
+    #[msg("Milestones can no longer be changed")]
+    MilestonesLocked,
+    #[msg("Project is not funded")]
+    ProjectNotFunded,
+    #[msg("No milestone left to release")]
+    NoMilestoneToRelease,
+    #[msg("Project has not been completed")]
+    ProjectNotCompleted,
+    #[msg("Only contributors with an outstanding contribution can claim a badge")]
+    NotAContributor,
+    #[msg("Completion badge already claimed")]
+    BadgeAlreadyClaimed,
+    #[msg("Project metadata can only be edited while the project is open")]
+    ProjectMetadataLocked,
+    #[msg("Project URI is too long")]
+    ProjectUriTooLong,
+    #[msg("New project authority must be set and differ from the current one")]
+    InvalidProjectAuthority,
+    #[msg("Verifier set or threshold is invalid")]
+    InvalidVerifierSet,
+    #[msg("Verifier is already registered")]
+    VerifierAlreadyRegistered,
+    #[msg("Verifier is not registered")]
+    VerifierNotRegistered,
+    #[msg("Not enough registered verifiers signed the receipt")]
+    VerifierQuorumNotMet,
+    #[msg("Receipt is still inside its challenge window")]
+    ChallengeWindowOpen,
+    #[msg("Receipt challenge window has closed")]
+    ChallengeWindowClosed,
+    #[msg("Receipt is under dispute")]
+    ReceiptDisputed,
+    #[msg("Receipt was rejected")]
+    ReceiptRejected,
+    #[msg("Receipt has no open dispute")]
+    ReceiptNotDisputed,
+    #[msg("Photo hash already backs another receipt")]
+    PhotoHashAlreadyClaimed,
+    #[msg("Receipt timestamp is older than the maximum receipt age")]
+    ReceiptTimestampStale,
+    #[msg("Receipt timestamp is too far in the future")]
+    ReceiptTimestampInFuture,
+    #[msg("Zone id must not be empty")]
+    InvalidZoneId,
+    #[msg("Zone bounding box is invalid")]
+    InvalidZoneBounds,
+    #[msg("Zone is inactive")]
+    ZoneInactive,
+    #[msg("Token mint does not match the material's mint")]
+    MaterialMintMismatch,
+    #[msg("Material vault has not been created")]
+    MaterialVaultMissing,
+    #[msg("Material vaults can only be created while the project is a draft")]
+    MaterialVaultsLocked,
+    #[msg("Receipt location is outside the zone bounds")]
+    ReceiptOutsideZone,
+    #[msg("Receipt can still be minted")]
+    ReceiptStillMintable,
 }
 
-// ── Account structs ───────────────────────────────────────────────────
+// ── Unit tests ────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player_initialize_sets_defaults() {
+        let authority = Pubkey::new_unique();
+        let mut profile = PlayerProfile {
+            authority: Pubkey::default(),
+            bump: 0,
+            credits_contributed: 3,
+            blocks_minted: 9,
+            brick_count: 0,
+            collections_count: 0,
+        };
+
+        profile.initialize(authority, 7).unwrap();
+        assert_eq!(profile.authority, authority);
+        assert_eq!(profile.bump, 7);
+        assert_eq!(profile.credits_contributed, 0);
+        assert_eq!(profile.blocks_minted, 0);
+        assert_eq!(profile.brick_count, 0);
+        assert_eq!(profile.collections_count, 0);
+    }
+
+    #[test]
+    fn project_pool_initialize_and_contribute() {
+        let authority = Pubkey::new_unique();
+        let mut pool = test_pool();
+        let targets = [
+            MaterialTarget { material_id: 1, target: 4 },
+            MaterialTarget { material_id: 2, target: 6 },
+        ];
+        pool.initialize(authority, 4, 42, &targets, "Community Garden", 1_700_000_000)
+            .unwrap();
+        assert_eq!(pool.authority, authority);
+        assert_eq!(pool.bump, 4);
+        assert_eq!(pool.id, 42);
+        assert_eq!(pool.goal_credits, 10);
+        assert_eq!(pool.received_credits, 0);
+        assert_eq!(pool.status, ProjectStatus::Draft);
+        assert_eq!(pool.name(), "Community Garden");
+
+        let mint = Pubkey::new_unique();
+        pool.bind_material_vault(2, mint, 254).unwrap();
+        pool.sync_material_vault(2, mint, 6).unwrap();
+        assert_eq!(pool.received_credits, 6);
+        assert_eq!(pool.material_goals().len(), 2);
+        assert_eq!(pool.material_goals()[1].mint, mint);
+        assert_eq!(pool.material_goals()[1].received, 6);
+    }
+
+    #[test]
+    fn player_record_mint_checks_overflow() {
+        let mut profile = PlayerProfile {
+            authority: Pubkey::default(),
+            bump: 1,
+            credits_contributed: 0,
+            blocks_minted: u64::MAX - 1,
+            brick_count: 0,
+            collections_count: 0,
+        };
+
+        profile.record_mint(1).unwrap();
+        assert_eq!(profile.collections_count, 1);
+        let err = profile.record_mint(1).unwrap_err();
+        assert_eq!(err, ErrorCode::Overflow.into());
+    }
+
+    #[test]
+    fn player_record_contribution_keeps_lifetime_stats() {
+        let mut profile = PlayerProfile {
+            authority: Pubkey::default(),
+            bump: 1,
+            credits_contributed: 0,
+            blocks_minted: 0,
+            brick_count: 0,
+            collections_count: 0,
+        };
+
+        profile.record_mint(10).unwrap();
+        profile.record_contribution(4).unwrap();
+        assert_eq!(profile.blocks_minted, 10);
+        assert_eq!(profile.credits_contributed, 4);
+
+        let err = profile.record_contribution(u64::MAX).unwrap_err();
+        assert_eq!(err, ErrorCode::Overflow.into());
+    }
+
+    #[test]
+    fn project_name_too_long() {
+        let mut pool = test_pool();
+        let long_name = "x".repeat(ProjectPool::NAME_MAX_LEN + 1);
+        let err = pool
+            .initialize(
+                Pubkey::new_unique(),
+                2,
+                5,
+                &[MaterialTarget { material_id: 0, target: 10 }],
+                &long_name,
+                1_700_000_000,
+            )
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::NameTooLong.into());
+    }
+
+    #[test]
+    fn poc_receipt_seed_derivation_matches() {
+        let player = Pubkey::new_unique();
+        let attestation_id = [7u8; 32];
+        let (pda, bump) =
+            ProofOfCollectionReceipt::derive_pda(&crate::ID, &player, &attestation_id);
+        let (expected, expected_bump) = Pubkey::find_program_address(
+            &[
+                ProofOfCollectionReceipt::SEED_PREFIX,
+                player.as_ref(),
+                &attestation_id,
+            ],
+            &crate::ID,
+        );
+
+        assert_eq!(pda, expected);
+        assert_eq!(bump, expected_bump);
+    }
+
+    fn empty_receipt() -> ProofOfCollectionReceipt {
+        ProofOfCollectionReceipt {
+            player: Pubkey::default(),
+            bump: 0,
+            attestation_id: [0u8; 32],
+            photo_hash: [0u8; 32],
+            zone_id_len: 0,
+            zone_id: [0u8; ProofOfCollectionReceipt::ZONE_ID_MAX_LEN],
+            location: GeoPoint::default(),
+            material_type: 0,
+            quantity: 0,
+            timestamp: 0,
+            approvals: 0,
+            redeemed: false,
+            status: ReceiptStatus::Pending,
+            challenge_ends_at: 0,
+            bond: 0,
+        }
+    }
+
+    /// An initialized receipt for `quantity` units of `material_type`.
+    fn test_receipt(material_type: u8, quantity: u64) -> ProofOfCollectionReceipt {
+        let mut receipt = empty_receipt();
+        receipt
+            .initialize(
+                Pubkey::new_unique(),
+                1,
+                [1u8; 32],
+                [2u8; 32],
+                "zone-1",
+                GeoPoint::default(),
+                material_type,
+                quantity,
+                1,
+            )
+            .unwrap();
+        receipt
+    }
+
+    #[test]
+    fn poc_receipt_validation_rejects_invalid_inputs() {
+        let mut receipt = empty_receipt();
+
+        let too_long_zone = "z".repeat(ProofOfCollectionReceipt::ZONE_ID_MAX_LEN + 1);
+        let err = receipt
+            .initialize(
+                Pubkey::new_unique(),
+                1,
+                [1u8; 32],
+                [2u8; 32],
+                &too_long_zone,
+                GeoPoint::default(),
+                0,
+                1,
+                1,
+            )
+            .unwrap_err();
+
+        assert_eq!(err, ErrorCode::ZoneIdTooLong.into());
+
+        let err = receipt
+            .initialize(
+                Pubkey::new_unique(),
+                1,
+                [1u8; 32],
+                [2u8; 32],
+                "zone-1",
+                GeoPoint::default(),
+                0,
+                0,
+                1,
+            )
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidAmount.into());
+    }
+
+    fn ed25519_instruction_data(pubkey: &Pubkey, message: &[u8]) -> Vec<u8> {
+        // Mirrors the layout produced by `Ed25519Program.createInstructionWithPublicKey`.
+        let pubkey_offset = (ED25519_OFFSETS_START + ED25519_OFFSETS_LEN) as u16;
+        let signature_offset = pubkey_offset + 32;
+        let message_offset = signature_offset + 64;
+        let mut data = vec![1u8, 0];
+        for value in [
+            signature_offset,
+            u16::MAX,
+            pubkey_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(pubkey.as_ref());
+        data.extend_from_slice(&[9u8; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn ed25519_instruction_parses_pubkey_and_message() {
+        let verifier = Pubkey::new_unique();
+        let data = ed25519_instruction_data(&verifier, b"receipt");
+
+        let entries = parse_ed25519_instruction(&data).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].pubkey, verifier);
+        assert_eq!(entries[0].signature, [9u8; 64]);
+        assert_eq!(entries[0].message, b"receipt");
+
+        // Offsets pointing at another instruction are rejected.
+        let mut foreign = data.clone();
+        foreign[4..6].copy_from_slice(&0u16.to_le_bytes());
+        let err = parse_ed25519_instruction(&foreign).unwrap_err();
+        assert_eq!(err, ErrorCode::MalformedSignatureInstruction.into());
+
+        let err = parse_ed25519_instruction(&data[..20]).unwrap_err();
+        assert_eq!(err, ErrorCode::MalformedSignatureInstruction.into());
+    }
+
+    #[test]
+    fn poc_receipt_attestation_message_is_canonical() {
+        let player = Pubkey::new_unique();
+        let mut receipt = empty_receipt();
+        let location = GeoPoint {
+            lat_e7: -12_500_000,
+            lon_e7: 368_500_000,
+        };
+        receipt
+            .initialize(
+                player,
+                1,
+                [3u8; 32],
+                [4u8; 32],
+                "zone-1",
+                location,
+                2,
+                5,
+                1_700_000_000,
+            )
+            .unwrap();
+
+        let message = receipt.attestation_message();
+        let domain_len = ProofOfCollectionReceipt::ATTESTATION_DOMAIN.len();
+        assert_eq!(&message[..domain_len], ProofOfCollectionReceipt::ATTESTATION_DOMAIN);
+        assert_eq!(&message[domain_len..domain_len + 32], player.as_ref());
+        assert_eq!(message[domain_len + 96], 6);
+        assert_eq!(&message[domain_len + 97..domain_len + 103], b"zone-1");
+        assert_eq!(
+            &message[domain_len + 103..domain_len + 107],
+            &(-12_500_000i32).to_le_bytes()
+        );
+        assert_eq!(
+            &message[domain_len + 107..domain_len + 111],
+            &368_500_000i32.to_le_bytes()
+        );
+        assert_eq!(message[domain_len + 111], 2);
+        assert_eq!(&message[domain_len + 112..domain_len + 120], &5u64.to_le_bytes());
+        assert_eq!(message.len(), domain_len + 128);
+        assert_eq!(receipt.attestation_hash(), hash(&message).to_bytes());
+    }
+
+    fn test_registry(verifiers: &[Pubkey], threshold: u8) -> VerifierRegistry {
+        let mut registry = VerifierRegistry {
+            bump: 0,
+            threshold: 0,
+            verifiers_len: 0,
+            verifiers: [Pubkey::default(); VerifierRegistry::MAX_VERIFIERS],
+        };
+        registry.initialize(254, verifiers, threshold).unwrap();
+        registry
+    }
 
-#[account]
-pub struct GlobalConfig {
-    pub authority: Pubkey,
-    pub block_mint: Pubkey,
-    pub total_blocks_minted: u64,
-    pub total_bricks_created: u64,
-    pub bump: u8,
-}
+    #[test]
+    fn verifier_registry_requires_distinct_registered_quorum() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let registry = test_registry(&[a, b, c], 2);
+        let outsider = Pubkey::new_unique();
 
-impl GlobalConfig {
-    pub const SEED_PREFIX: &'static [u8] = b"global_config";
-    pub const SIZE: usize = 8  // discriminator
-        + 32                   // authority
-        + 32                   // block_mint
-        + 8                    // total_blocks_minted
-        + 8                    // total_bricks_created
-        + 1;                   // bump
-}
+        let err = registry.require_quorum(&[a]).unwrap_err();
+        assert_eq!(err, ErrorCode::VerifierQuorumNotMet.into());
+        // Duplicates and unregistered keys do not count towards the quorum
+        let err = registry.require_quorum(&[a, a, outsider]).unwrap_err();
+        assert_eq!(err, ErrorCode::VerifierQuorumNotMet.into());
+        assert_eq!(registry.require_quorum(&[a, outsider, c]).unwrap(), 2);
+        assert_eq!(registry.require_quorum(&[a, b, c]).unwrap(), 3);
+    }
 
-#[account]
-pub struct PlayerProfile {
-    pub authority: Pubkey,
-    pub bump: u8,
-    pub total_credits: u64,
-    pub blocks_minted: u64,
-    pub brick_count: u64,
-    pub collections_count: u64,
-}
+    #[test]
+    fn verifier_registry_management() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut registry = VerifierRegistry {
+            bump: 0,
+            threshold: 0,
+            verifiers_len: 0,
+            verifiers: [Pubkey::default(); VerifierRegistry::MAX_VERIFIERS],
+        };
+        let err = registry.initialize(254, &[a, a], 1).unwrap_err();
+        assert_eq!(err, ErrorCode::VerifierAlreadyRegistered.into());
+        let err = registry.initialize(254, &[a], 2).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidVerifierSet.into());
 
-impl PlayerProfile {
-    pub const SEED_PREFIX: &'static [u8] = b"player";
-    pub const SIZE: usize = 8  // discriminator
-        + 32                   // authority pubkey
-        + 1                    // bump
-        + 8                    // total credits
-        + 8                    // blocks_minted
-        + 8                    // brick_count
-        + 8;                   // collections_count
+        let mut registry = test_registry(&[a, b], 2);
+        // Removing either key would leave the threshold unreachable
+        let err = registry.remove_verifier(a).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidVerifierSet.into());
+        registry.set_threshold(1).unwrap();
+        registry.remove_verifier(a).unwrap();
+        assert_eq!(registry.verifiers(), &[b]);
+        let err = registry.remove_verifier(a).unwrap_err();
+        assert_eq!(err, ErrorCode::VerifierNotRegistered.into());
 
-    pub fn initialize(&mut self, authority: Pubkey, bump: u8) -> Result<()> {
-        self.authority = authority;
-        self.bump = bump;
-        self.total_credits = 0;
-        self.blocks_minted = 0;
-        self.brick_count = 0;
-        self.collections_count = 0;
-        Ok(())
+        let rotated = Pubkey::new_unique();
+        let err = registry.rotate_verifier(a, rotated).unwrap_err();
+        assert_eq!(err, ErrorCode::VerifierNotRegistered.into());
+        registry.rotate_verifier(b, rotated).unwrap();
+        assert_eq!(registry.verifiers(), &[rotated]);
+        let err = registry.set_threshold(0).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidVerifierSet.into());
+
+        assert_eq!(
+            VerifierRegistry::SIZE,
+            8 + 1 + 1 + 1 + VerifierRegistry::MAX_VERIFIERS * 32
+        );
     }
 
-    pub fn add_credits(&mut self, amount: u64) -> Result<()> {
-        self.total_credits = self
-            .total_credits
-            .checked_add(amount)
-            .ok_or(ErrorCode::Overflow)?;
-        Ok(())
+    #[test]
+    fn poc_receipt_redeem_converts_once() {
+        let mut receipt = test_receipt(3, 9);
+
+        let err = receipt.redeem(&test_material(2, 4), 1).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidMaterialType.into());
+        let mut retired = test_material(3, 4);
+        retired.update(false, 4, None).unwrap();
+        let err = receipt.redeem(&retired, 1).unwrap_err();
+        assert_eq!(err, ErrorCode::MaterialInactive.into());
+        assert!(!receipt.redeemed);
+
+        assert_eq!(receipt.redeem(&test_material(3, 4), 1).unwrap(), 2);
+        assert!(receipt.redeemed);
+        let err = receipt.redeem(&test_material(3, 4), 1).unwrap_err();
+        assert_eq!(err, ErrorCode::ReceiptAlreadyRedeemed.into());
     }
-}
 
-#[account]
-pub struct ProjectPool {
-    pub authority: Pubkey,
-    pub bump: u8,
-    pub seed: u64,
-    pub goal_credits: u64,
-    pub received_credits: u64,
-    pub name_len: u8,
-    pub name: [u8; ProjectPool::NAME_MAX_LEN],
-}
+    #[test]
+    fn poc_receipt_challenge_window_and_disputes() {
+        let mut receipt = test_receipt(3, 8);
+        receipt.open_challenge_window(1_000, 100, 50).unwrap();
+        assert_eq!(receipt.challenge_ends_at, 1_100);
 
-impl ProjectPool {
-    pub const SEED_PREFIX: &'static [u8] = b"project";
-    pub const NAME_MAX_LEN: usize = 32;
-    pub const SIZE: usize = 8  // discriminator
-        + 32                   // authority
-        + 1                    // bump
-        + 8                    // seed
-        + 8                    // goal credits
-        + 8                    // received credits
-        + 1                    // name length
-        + Self::NAME_MAX_LEN;  // name bytes
+        let material = test_material(3, 4);
+        let err = receipt.redeem(&material, 1_099).unwrap_err();
+        assert_eq!(err, ErrorCode::ChallengeWindowOpen.into());
+        let err = receipt.resolve_dispute(true).unwrap_err();
+        assert_eq!(err, ErrorCode::ReceiptNotDisputed.into());
 
-    pub fn initialize(
-        &mut self,
-        authority: Pubkey,
-        bump: u8,
-        seed: u64,
-        goal: u64,
-        name: &str,
-    ) -> Result<()> {
-        if goal == 0 {
-            return Err(ErrorCode::InvalidAmount.into());
-        }
-        if name.as_bytes().len() > Self::NAME_MAX_LEN {
-            return Err(ErrorCode::NameTooLong.into());
-        }
+        // Dismissed: back to pending, mintable once the window closes
+        receipt.open_dispute(1_050).unwrap();
+        let err = receipt.open_dispute(1_060).unwrap_err();
+        assert_eq!(err, ErrorCode::ReceiptDisputed.into());
+        let err = receipt.redeem(&material, 2_000).unwrap_err();
+        assert_eq!(err, ErrorCode::ReceiptDisputed.into());
+        receipt.resolve_dispute(false).unwrap();
+        assert_eq!(receipt.status, ReceiptStatus::Pending);
+        let err = receipt.open_dispute(1_100).unwrap_err();
+        assert_eq!(err, ErrorCode::ChallengeWindowClosed.into());
 
-        self.authority = authority;
-        self.bump = bump;
-        self.seed = seed;
-        self.goal_credits = goal;
-        self.received_credits = 0;
-        self.name_len = name.len() as u8;
-        self.name = [0u8; Self::NAME_MAX_LEN];
-        self.name[..name.len()].copy_from_slice(name.as_bytes());
-        Ok(())
+        // Upheld: rejected for good and the bond goes to the challenger
+        let mut rejected = receipt.clone();
+        rejected.status = ReceiptStatus::Disputed;
+        rejected.resolve_dispute(true).unwrap();
+        let err = rejected.redeem(&material, 2_000).unwrap_err();
+        assert_eq!(err, ErrorCode::ReceiptRejected.into());
+        assert_eq!(rejected.take_bond(), 50);
+        assert_eq!(rejected.bond, 0);
+
+        assert_eq!(receipt.redeem(&material, 1_100).unwrap(), 2);
+        assert_eq!(
+            ProofOfCollectionReceipt::SIZE,
+            8 + 32 + 1 + 32 + 32 + 1 + 32 + 8 + 1 + 8 + 8 + 1 + 1 + 1 + 8 + 8
+        );
     }
 
-    pub fn record_contribution(&mut self, amount: u64) -> Result<()> {
-        self.received_credits = self
-            .received_credits
-            .checked_add(amount)
-            .ok_or(ErrorCode::Overflow)?;
-        Ok(())
+    #[test]
+    fn poc_receipt_closes_only_when_unmintable() {
+        let mut receipt = test_receipt(3, 9);
+        receipt.open_challenge_window(1_000, 100, 50).unwrap();
+        let material = test_material(3, 4);
+        let err = receipt.require_unmintable(&material, 1_100).unwrap_err();
+        assert_eq!(err, ErrorCode::ReceiptStillMintable.into());
+
+        // Repriced above the receipt's quantity
+        let repriced = test_material(3, 10);
+        let err = receipt.require_unmintable(&repriced, 1_099).unwrap_err();
+        assert_eq!(err, ErrorCode::ChallengeWindowOpen.into());
+        receipt.require_unmintable(&repriced, 1_100).unwrap();
+        let err = receipt.redeem(&repriced, 1_100).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidAmount.into());
+
+        // Retired after submission
+        let mut retired = test_material(3, 4);
+        retired.update(false, 4, None).unwrap();
+        receipt.require_unmintable(&retired, 1_100).unwrap();
+
+        // Disputed or already minted receipts are settled elsewhere
+        let mut disputed = receipt.clone();
+        disputed.status = ReceiptStatus::Disputed;
+        let err = disputed.require_unmintable(&retired, 1_100).unwrap_err();
+        assert_eq!(err, ErrorCode::ReceiptDisputed.into());
+        receipt.redeem(&material, 1_100).unwrap();
+        let err = receipt.require_unmintable(&retired, 1_100).unwrap_err();
+        assert_eq!(err, ErrorCode::ReceiptAlreadyRedeemed.into());
     }
 
-    pub fn name(&self) -> String {
-        let bytes = &self.name[..self.name_len as usize];
-        String::from_utf8(bytes.to_vec()).unwrap_or_default()
+    #[test]
+    fn photo_hash_claims_once() {
+        let mut claim = PhotoHashClaim {
+            photo_hash: [0u8; 32],
+            receipt: Pubkey::default(),
+            player: Pubkey::default(),
+            bump: 0,
+            claimed_at: 0,
+        };
+        let receipt = Pubkey::new_unique();
+        let player = Pubkey::new_unique();
+        claim.claim([7u8; 32], receipt, player, 253, 100).unwrap();
+        assert_eq!(claim.receipt, receipt);
+        assert_eq!(claim.player, player);
+
+        // Same photo from another player or attestation id
+        let err = claim
+            .claim([7u8; 32], Pubkey::new_unique(), Pubkey::new_unique(), 253, 200)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::PhotoHashAlreadyClaimed.into());
+        assert_eq!(claim.receipt, receipt);
+        assert_eq!(claim.claimed_at, 100);
+        assert_eq!(PhotoHashClaim::SIZE, 8 + 32 + 32 + 32 + 1 + 8);
     }
-}
 
-#[account]
-pub struct ProofOfCollectionReceipt {
-    pub player: Pubkey,
-    pub bump: u8,
-    pub attestation_id: [u8; 32],
-    pub photo_hash: [u8; 32],
-    pub zone_id_len: u8,
-    pub zone_id: [u8; ProofOfCollectionReceipt::ZONE_ID_MAX_LEN],
-    pub material_type: u8,
-    pub quantity: u64,
-    pub timestamp: i64,
-}
+    #[test]
+    fn zone_tracks_per_material_totals() {
+        let bounds = ZoneBounds {
+            min_lat_e7: -13_000_000,
+            min_lon_e7: 368_000_000,
+            max_lat_e7: -12_000_000,
+            max_lon_e7: 369_000_000,
+        };
+        let mut zone = Zone {
+            bump: 0,
+            active: false,
+            zone_id_len: 0,
+            zone_id: [0u8; ProofOfCollectionReceipt::ZONE_ID_MAX_LEN],
+            name_len: 0,
+            name: [0u8; Zone::NAME_MAX_LEN],
+            bounds: ZoneBounds::default(),
+            receipts_count: 0,
+            material_totals: [0u64; Zone::MAX_MATERIALS],
+        };
+        let err = zone.initialize(250, "", "Beach", bounds).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidZoneId.into());
+        zone.initialize(250, "zone-1", "Beach", bounds).unwrap();
+        assert!(zone.active);
+        assert_eq!(zone.zone_id(), "zone-1");
+        assert_eq!(zone.name(), "Beach");
+        assert!(zone.bounds.contains(-12_500_000, 368_500_000));
+        assert!(!zone.bounds.contains(-11_000_000, 368_500_000));
 
-impl ProofOfCollectionReceipt {
-    pub const SEED_PREFIX: &'static [u8] = b"poc";
-    pub const ZONE_ID_MAX_LEN: usize = 32;
-    pub const SIZE: usize = 8  // discriminator
-        + 32                   // player pubkey
-        + 1                    // bump
-        + 32                   // attestation id
-        + 32                   // photo hash
-        + 1                    // zone id length
-        + Self::ZONE_ID_MAX_LEN // zone id bytes
-        + 1                    // material type
-        + 8                    // quantity
-        + 8;                   // timestamp
+        zone.record_collection(2, 5).unwrap();
+        zone.record_collection(2, 7).unwrap();
+        zone.record_collection(0, 1).unwrap();
+        assert_eq!(zone.material_totals[2], 12);
+        assert_eq!(zone.material_totals[0], 1);
+        assert_eq!(zone.receipts_count, 3);
 
-    pub fn initialize(
-        &mut self,
-        player: Pubkey,
-        bump: u8,
-        attestation_id: [u8; 32],
-        photo_hash: [u8; 32],
-        zone_id: &str,
-        material_type: u8,
-        quantity: u64,
-        timestamp: i64,
-    ) -> Result<()> {
-        if quantity == 0 {
-            return Err(ErrorCode::InvalidAmount.into());
-        }
-        if timestamp <= 0 {
-            return Err(ErrorCode::InvalidTimestamp.into());
-        }
-        if zone_id.as_bytes().len() > Self::ZONE_ID_MAX_LEN {
-            return Err(ErrorCode::ZoneIdTooLong.into());
-        }
-        MaterialType::try_from(material_type)?;
+        // Every id the registry can hand out has a slot
+        zone.record_collection(u8::MAX, 4).unwrap();
+        assert_eq!(zone.material_totals[u8::MAX as usize], 4);
+        let err = zone.record_collection(u8::MAX, u64::MAX).unwrap_err();
+        assert_eq!(err, ErrorCode::Overflow.into());
+        assert_eq!(zone.receipts_count, 4);
 
-        self.player = player;
-        self.bump = bump;
-        self.attestation_id = attestation_id;
-        self.photo_hash = photo_hash;
-        self.zone_id_len = zone_id.len() as u8;
-        self.zone_id = [0u8; Self::ZONE_ID_MAX_LEN];
-        self.zone_id[..zone_id.len()].copy_from_slice(zone_id.as_bytes());
-        self.material_type = material_type;
-        self.quantity = quantity;
-        self.timestamp = timestamp;
-        Ok(())
+        let inverted = ZoneBounds {
+            min_lat_e7: 1,
+            max_lat_e7: 0,
+            ..bounds
+        };
+        let err = zone.update(false, inverted).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidZoneBounds.into());
+        assert!(zone.active);
+        let out_of_range = ZoneBounds {
+            max_lon_e7: ZoneBounds::MAX_LON_E7 + 1,
+            ..bounds
+        };
+        let err = zone.update(false, out_of_range).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidZoneBounds.into());
+        zone.update(false, bounds).unwrap();
+        assert!(!zone.active);
+        assert_eq!(Zone::SIZE, 8 + 1 + 1 + 1 + 32 + 1 + 32 + 16 + 8 + 256 * 8);
     }
 
-    pub fn derive_pda(program_id: &Pubkey, player: &Pubkey, attestation_id: &[u8; 32]) -> (Pubkey, u8) {
-        Pubkey::find_program_address(
-            &[Self::SEED_PREFIX, player.as_ref(), attestation_id],
-            program_id,
-        )
+    fn test_material(material_id: u8, units_per_block: u64) -> MaterialConfig {
+        let mut material = MaterialConfig {
+            material_id: 0,
+            bump: 0,
+            active: false,
+            units_per_block: 0,
+            mint: None,
+            name_len: 0,
+            name: [0u8; MaterialConfig::NAME_MAX_LEN],
+        };
+        material
+            .initialize(material_id, 254, "Material", units_per_block, None)
+            .unwrap();
+        material
     }
 
-    pub fn zone_id(&self) -> String {
-        let bytes = &self.zone_id[..self.zone_id_len as usize];
-        String::from_utf8(bytes.to_vec()).unwrap_or_default()
+    #[test]
+    fn material_config_initialize_and_convert() {
+        let mint = Pubkey::new_unique();
+        let mut material = test_material(4, 2);
+        assert!(material.active);
+        assert_eq!(material.name(), "Material");
+        assert_eq!(material.blocks_for(5).unwrap(), 2);
+        let err = material.blocks_for(1).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidAmount.into());
+
+        material.update(false, 1, Some(mint)).unwrap();
+        assert!(!material.active);
+        assert_eq!(material.mint, Some(mint));
+        assert_eq!(material.blocks_for(3).unwrap(), 3);
+
+        let err = material.update(true, 0, None).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidAmount.into());
+
+        let long_name = "e".repeat(MaterialConfig::NAME_MAX_LEN + 1);
+        let err = material.initialize(5, 1, &long_name, 1, None).unwrap_err();
+        assert_eq!(err, ErrorCode::NameTooLong.into());
     }
-}
 
-// ── Enums ─────────────────────────────────────────────────────────────
+    #[test]
+    fn material_config_rejects_unknown_and_inactive_ids() {
+        let mut material = test_material(4, 2);
+        material.require_usable(4).unwrap();
+        let err = material.require_usable(9).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidMaterialType.into());
 
-#[repr(u8)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum MaterialType {
-    Plastic = 0,
-    Glass = 1,
-    Metal = 2,
-    Paper = 3,
-}
+        material.update(false, 2, None).unwrap();
+        let err = material.require_usable(4).unwrap_err();
+        assert_eq!(err, ErrorCode::MaterialInactive.into());
+    }
+
+    #[test]
+    fn global_config_size_is_correct() {
+        assert_eq!(
+            GlobalConfig::SIZE,
+            8 + 32 + 32 + 32 + 32 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 32 + 32 + 8 + 8 + 8 + 1
+        );
+    }
 
-impl MaterialType {
-    pub fn try_from(value: u8) -> Result<Self> {
-        match value {
-            0 => Ok(Self::Plastic),
-            1 => Ok(Self::Glass),
-            2 => Ok(Self::Metal),
-            3 => Ok(Self::Paper),
-            _ => Err(ErrorCode::InvalidMaterialType.into()),
+    fn test_config(authority: Pubkey) -> GlobalConfig {
+        GlobalConfig {
+            authority,
+            pending_authority: Pubkey::default(),
+            verifier: Pubkey::new_unique(),
+            curator: authority,
+            pauser: authority,
+            arbiter: authority,
+            paused: 0,
+            blocks_per_brick: DEFAULT_BLOCKS_PER_BRICK,
+            challenge_period: DEFAULT_CHALLENGE_PERIOD,
+            dispute_bond: DEFAULT_DISPUTE_BOND,
+            max_receipt_age: DEFAULT_MAX_RECEIPT_AGE,
+            max_future_skew: DEFAULT_MAX_FUTURE_SKEW,
+            block_mint: Pubkey::new_unique(),
+            brick_mint: Pubkey::new_unique(),
+            total_blocks_minted: 0,
+            total_bricks_created: 0,
+            project_count: 0,
+            bump: 255,
         }
     }
-}
 
-// ── Events ────────────────────────────────────────────────────────────
+    #[test]
+    fn global_config_authority_transfer_is_two_step() {
+        let authority = Pubkey::new_unique();
+        let multisig = Pubkey::new_unique();
+        let mut config = test_config(authority);
 
-#[event]
-pub struct BlocksMinted {
-    pub player: Pubkey,
-    pub amount: u64,
-    pub waste_type: u8,
-    pub timestamp: i64,
-}
+        // Nothing pending: nobody can accept
+        let err = config.accept_authority(multisig).unwrap_err();
+        assert_eq!(err, ErrorCode::Unauthorized.into());
 
-#[event]
-pub struct BrickConverted {
-    pub player: Pubkey,
-    pub new_brick_count: u64,
-    pub timestamp: i64,
-}
+        config.pending_authority = multisig;
+        let err = config.accept_authority(Pubkey::new_unique()).unwrap_err();
+        assert_eq!(err, ErrorCode::Unauthorized.into());
+        assert_eq!(config.authority, authority);
 
-// ── Errors ────────────────────────────────────────────────────────────
+        config.accept_authority(multisig).unwrap();
+        assert_eq!(config.authority, multisig);
+        assert_eq!(config.pending_authority, Pubkey::default());
+    }
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Amount must be greater than zero")]
-    InvalidAmount,
-    #[msg("Project name exceeds max length")]
-    NameTooLong,
-    #[msg("Zone id exceeds max length")]
-    ZoneIdTooLong,
-    #[msg("Material type is invalid")]
-    InvalidMaterialType,
-    #[msg("Timestamp is invalid")]
-    InvalidTimestamp,
-    #[msg("Arithmetic overflow")]
-    Overflow,
-    #[msg("Unauthorized signer")]
-    Unauthorized,
-    #[msg("Insufficient BLOCK tokens (need 10)")]
-    InsufficientBlocks,
-}
+    #[test]
+    fn global_config_apply_params_updates_only_set_fields() {
+        let mut config = test_config(Pubkey::new_unique());
+        let verifier = config.verifier;
 
-// ── Unit tests ────────────────────────────────────────────────────────
+        config.apply_params(&ConfigParams::default()).unwrap();
+        assert_eq!(config.verifier, verifier);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let rotated = Pubkey::new_unique();
+        config
+            .apply_params(&ConfigParams {
+                verifier: Some(rotated),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(config.verifier, rotated);
+
+        let err = config
+            .apply_params(&ConfigParams {
+                verifier: Some(Pubkey::default()),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidConfigParam.into());
+    }
 
     #[test]
-    fn player_initialize_sets_defaults() {
-        let authority = Pubkey::new_unique();
-        let mut profile = PlayerProfile {
-            authority: Pubkey::default(),
-            bump: 0,
-            total_credits: 12,
-            blocks_minted: 0,
-            brick_count: 0,
-            collections_count: 0,
-        };
+    fn global_config_pause_flags_are_granular() {
+        let mut config = test_config(Pubkey::new_unique());
+        config.require_not_paused(GlobalConfig::PAUSE_MINT_BLOCKS).unwrap();
 
-        profile.initialize(authority, 7).unwrap();
-        assert_eq!(profile.authority, authority);
-        assert_eq!(profile.bump, 7);
-        assert_eq!(profile.total_credits, 0);
-        assert_eq!(profile.blocks_minted, 0);
-        assert_eq!(profile.brick_count, 0);
-        assert_eq!(profile.collections_count, 0);
+        config
+            .set_paused(GlobalConfig::PAUSE_MINT_BLOCKS | GlobalConfig::PAUSE_CONTRIBUTE, true)
+            .unwrap();
+        let err = config.require_not_paused(GlobalConfig::PAUSE_MINT_BLOCKS).unwrap_err();
+        assert_eq!(err, ErrorCode::Paused.into());
+        config.require_not_paused(GlobalConfig::PAUSE_CONVERT_TO_BRICK).unwrap();
+
+        config.set_paused(GlobalConfig::PAUSE_MINT_BLOCKS, false).unwrap();
+        config.require_not_paused(GlobalConfig::PAUSE_MINT_BLOCKS).unwrap();
+        assert_eq!(config.paused, GlobalConfig::PAUSE_CONTRIBUTE);
+
+        let err = config.set_paused(0, true).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidPauseFlags.into());
+        let err = config.set_paused(1 << 7, true).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidPauseFlags.into());
     }
 
     #[test]
-    fn project_pool_initialize_and_contribute() {
-        let authority = Pubkey::new_unique();
+    fn global_config_roles_are_independent() {
+        let admin = Pubkey::new_unique();
+        let mut config = test_config(admin);
+        let verifier = config.verifier;
+
+        let curator = Pubkey::new_unique();
+        let pauser = Pubkey::new_unique();
+        config
+            .apply_params(&ConfigParams {
+                curator: Some(curator),
+                pauser: Some(pauser),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(config.authority, admin);
+        assert_eq!(config.verifier, verifier);
+        assert_eq!(config.curator, curator);
+        assert_eq!(config.pauser, pauser);
+
+        let arbiter = Pubkey::new_unique();
+        config
+            .apply_params(&ConfigParams {
+                arbiter: Some(arbiter),
+                challenge_period: Some(3_600),
+                dispute_bond: Some(0),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(config.arbiter, arbiter);
+        assert_eq!(config.challenge_period, 3_600);
+        assert_eq!(config.dispute_bond, 0);
+        let err = config
+            .apply_params(&ConfigParams {
+                challenge_period: Some(-1),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidConfigParam.into());
+    }
+
+    #[test]
+    fn player_profile_size_includes_new_fields() {
+        assert_eq!(PlayerProfile::SIZE, 8 + 32 + 1 + 8 + 8 + 8 + 8);
+    }
+
+    #[test]
+    fn project_pool_size_includes_material_vaults() {
+        assert_eq!(
+            ProjectPool::SIZE,
+            8 + 32 + 32 + 1 + 8 + 8 + 8 + 1 + ProjectPool::NAME_MAX_LEN + 1 + 8
+                + 1
+                + ProjectPool::MAX_MATERIAL_GOALS * 58
+                + 32
+                + 1
+                + ProjectPool::MAX_MILESTONES * 34
+                + 1
+                + 8
+        );
+    }
+
+    fn test_pool() -> ProjectPool {
         let mut pool = ProjectPool {
             authority: Pubkey::default(),
+            pending_authority: Pubkey::default(),
             bump: 0,
-            seed: 0,
+            id: 0,
             goal_credits: 0,
             received_credits: 0,
             name_len: 0,
             name: [0u8; ProjectPool::NAME_MAX_LEN],
+            status: ProjectStatus::Draft,
+            deadline: 0,
+            material_goals_len: 0,
+            material_goals: [MaterialGoal::default(); ProjectPool::MAX_MATERIAL_GOALS],
+            builder: Pubkey::default(),
+            milestones_len: 0,
+            milestones: [Milestone::default(); ProjectPool::MAX_MILESTONES],
+            milestones_released: 0,
+            released_credits: 0,
         };
+        let targets = [
+            MaterialTarget { material_id: 0, target: 6 },
+            MaterialTarget { material_id: 1, target: 4 },
+        ];
+        pool.initialize(Pubkey::new_unique(), 1, 1, &targets, "Bench", 1_000)
+            .unwrap();
+        pool.bind_material_vault(0, Pubkey::new_unique(), 255).unwrap();
+        pool.bind_material_vault(1, Pubkey::new_unique(), 255).unwrap();
+        pool.set_milestones(Pubkey::new_unique(), &[milestone(10_000)])
+            .unwrap();
+        pool
+    }
 
-        pool.initialize(authority, 4, 42, 10, "Community Garden")
+    /// Mirrors a vault balance for `material_id` in the pool's test vault.
+    fn fund(pool: &mut ProjectPool, material_id: u8, vault_balance: u64) {
+        let mint = pool.material_goals()[pool.goal_index(material_id).unwrap()].mint;
+        pool.sync_material_vault(material_id, mint, vault_balance)
             .unwrap();
-        assert_eq!(pool.authority, authority);
-        assert_eq!(pool.bump, 4);
-        assert_eq!(pool.seed, 42);
+    }
+
+    #[test]
+    fn project_pool_lifecycle_happy_path() {
+        let mut pool = test_pool();
+        assert_eq!(pool.transition(ProjectStatus::Funding).unwrap(), ProjectStatus::Draft);
+        assert_eq!(pool.transition(ProjectStatus::Funded).unwrap(), ProjectStatus::Funding);
+        let err = pool.transition(ProjectStatus::Completed).unwrap_err();
+        assert_eq!(err, ErrorCode::ProjectNotCompletable.into());
+        pool.release_next_milestone(&[6, 4]).unwrap();
+        assert_eq!(pool.transition(ProjectStatus::Completed).unwrap(), ProjectStatus::Funded);
+        assert_eq!(pool.transition(ProjectStatus::Archived).unwrap(), ProjectStatus::Completed);
+        assert_eq!(pool.status, ProjectStatus::Archived);
+    }
+
+    #[test]
+    fn project_pool_lifecycle_rejects_illegal_transitions() {
+        let mut pool = test_pool();
+        let err = pool.transition(ProjectStatus::Completed).unwrap_err();
+        assert_eq!(err, ErrorCode::ProjectNotCompletable.into());
+        let err = pool.transition(ProjectStatus::Archived).unwrap_err();
+        assert_eq!(err, ErrorCode::ProjectNotArchivable.into());
+        let err = pool.transition(ProjectStatus::Funded).unwrap_err();
+        assert_eq!(err, ErrorCode::ProjectNotFunding.into());
+
+        pool.transition(ProjectStatus::Funding).unwrap();
+        let err = pool.transition(ProjectStatus::Funding).unwrap_err();
+        assert_eq!(err, ErrorCode::ProjectNotOpenable.into());
+
+        pool.transition(ProjectStatus::Cancelled).unwrap();
+        let err = pool.transition(ProjectStatus::Cancelled).unwrap_err();
+        assert_eq!(err, ErrorCode::ProjectNotCancellable.into());
+        let err = pool.transition(ProjectStatus::Funding).unwrap_err();
+        assert_eq!(err, ErrorCode::ProjectNotOpenable.into());
+        pool.transition(ProjectStatus::Archived).unwrap();
+    }
+
+    #[test]
+    fn project_pool_refundable_when_cancelled_or_expired() {
+        let mut pool = test_pool();
+        assert!(!pool.is_refundable(2_000));
+
+        pool.transition(ProjectStatus::Funding).unwrap();
+        fund(&mut pool, 0, 4);
+        assert!(!pool.is_expired(1_000));
+        assert!(!pool.is_refundable(1_000));
+        assert!(pool.is_expired(1_001));
+        assert!(pool.is_refundable(1_001));
+
+        // Reaching every goal before the deadline is never refundable
+        fund(&mut pool, 0, 6);
+        fund(&mut pool, 1, 4);
+        assert!(!pool.is_expired(1_001));
+        pool.transition(ProjectStatus::Funded).unwrap();
+        assert!(!pool.is_refundable(5_000));
+
+        pool.transition(ProjectStatus::Cancelled).unwrap();
+        assert!(pool.is_refundable(0));
+    }
+
+    #[test]
+    fn project_pool_funded_only_when_every_material_met() {
+        let mut pool = test_pool();
         assert_eq!(pool.goal_credits, 10);
+        assert!(!pool.goals_met());
+
+        // Overshooting one material does not cover another
+        fund(&mut pool, 0, 9);
+        assert!(!pool.goals_met());
+        fund(&mut pool, 1, 4);
+        assert!(pool.goals_met());
+        assert_eq!(pool.received_credits, 13);
+
+        let err = pool.sync_material_vault(7, Pubkey::new_unique(), 1).unwrap_err();
+        assert_eq!(err, ErrorCode::MaterialNotInProject.into());
+    }
+
+    #[test]
+    fn project_pool_binds_each_material_to_its_vault_mint() {
+        let mut pool = test_pool();
+        let brick = pool.material_goals()[0].mint;
+        assert_eq!(pool.goal_mint(0), Some(brick));
+        assert_eq!(pool.goal_mint(9), None);
+
+        // A deposit of one material's token never counts toward another
+        let err = pool.sync_material_vault(1, brick, 4).unwrap_err();
+        assert_eq!(err, ErrorCode::MaterialMintMismatch.into());
         assert_eq!(pool.received_credits, 0);
-        assert_eq!(pool.name(), "Community Garden");
 
-        pool.record_contribution(6).unwrap();
-        assert_eq!(pool.received_credits, 6);
+        let err = pool
+            .bind_material_vault(9, Pubkey::new_unique(), 255)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::MaterialNotInProject.into());
 
-        let err = pool.record_contribution(u64::MAX).unwrap_err();
-        assert_eq!(err, ErrorCode::Overflow.into());
+        pool.transition(ProjectStatus::Funding).unwrap();
+        let err = pool
+            .bind_material_vault(0, Pubkey::new_unique(), 255)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::MaterialVaultsLocked.into());
     }
 
     #[test]
-    fn player_add_credits_checks_overflow() {
-        let mut profile = PlayerProfile {
-            authority: Pubkey::default(),
-            bump: 1,
-            total_credits: u64::MAX,
-            blocks_minted: 0,
-            brick_count: 0,
-            collections_count: 0,
-        };
+    fn project_pool_opens_only_with_vaults_and_plan() {
+        let mut pool = test_pool();
+        pool.material_goals[1].mint = Pubkey::default();
+        assert_eq!(pool.goal_mint(1), None);
+        let err = pool.sync_material_vault(1, Pubkey::default(), 1).unwrap_err();
+        assert_eq!(err, ErrorCode::MaterialMintMismatch.into());
+        let err = pool.transition(ProjectStatus::Funding).unwrap_err();
+        assert_eq!(err, ErrorCode::MaterialVaultMissing.into());
 
-        let err = profile.add_credits(1).unwrap_err();
-        assert_eq!(err, ErrorCode::Overflow.into());
+        pool.bind_material_vault(1, Pubkey::new_unique(), 255).unwrap();
+        pool.milestones_len = 0;
+        let err = pool.transition(ProjectStatus::Funding).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidMilestones.into());
+
+        pool.set_milestones(Pubkey::new_unique(), &[milestone(10_000)])
+            .unwrap();
+        pool.transition(ProjectStatus::Funding).unwrap();
     }
 
     #[test]
-    fn project_name_too_long() {
-        let mut pool = ProjectPool {
-            authority: Pubkey::default(),
-            bump: 0,
-            seed: 0,
-            goal_credits: 0,
-            received_credits: 0,
-            name_len: 0,
-            name: [0u8; ProjectPool::NAME_MAX_LEN],
-        };
+    fn project_pool_rejects_invalid_material_goals() {
+        let mut pool = test_pool();
+        let err = pool
+            .initialize(Pubkey::new_unique(), 1, 1, &[], "Bench", 1_000)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidMaterialGoals.into());
 
-        let long_name = "x".repeat(ProjectPool::NAME_MAX_LEN + 1);
+        let duplicated = [
+            MaterialTarget { material_id: 2, target: 1 },
+            MaterialTarget { material_id: 2, target: 3 },
+        ];
         let err = pool
-            .initialize(Pubkey::new_unique(), 2, 5, 10, &long_name)
+            .initialize(Pubkey::new_unique(), 1, 1, &duplicated, "Bench", 1_000)
             .unwrap_err();
-        assert_eq!(err, ErrorCode::NameTooLong.into());
+        assert_eq!(err, ErrorCode::InvalidMaterialGoals.into());
+
+        let zero = [MaterialTarget { material_id: 2, target: 0 }];
+        let err = pool
+            .initialize(Pubkey::new_unique(), 1, 1, &zero, "Bench", 1_000)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidAmount.into());
+    }
+
+    fn milestone(release_bps: u16) -> Milestone {
+        Milestone {
+            description_hash: [release_bps as u8; 32],
+            release_bps,
+        }
     }
 
     #[test]
-    fn poc_receipt_seed_derivation_matches() {
-        let player = Pubkey::new_unique();
-        let attestation_id = [7u8; 32];
-        let (pda, bump) =
-            ProofOfCollectionReceipt::derive_pda(&crate::ID, &player, &attestation_id);
-        let (expected, expected_bump) = Pubkey::find_program_address(
-            &[
-                ProofOfCollectionReceipt::SEED_PREFIX,
-                player.as_ref(),
-                &attestation_id,
-            ],
-            &crate::ID,
-        );
+    fn project_pool_milestones_must_cover_whole_pool() {
+        let mut pool = test_pool();
+        let builder = Pubkey::new_unique();
 
-        assert_eq!(pda, expected);
-        assert_eq!(bump, expected_bump);
+        let err = pool
+            .set_milestones(builder, &[milestone(5_000), milestone(4_000)])
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidMilestones.into());
+        let err = pool
+            .set_milestones(builder, &[milestone(10_000), milestone(0)])
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidMilestones.into());
+        let err = pool
+            .set_milestones(Pubkey::default(), &[milestone(10_000)])
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidMilestones.into());
+
+        pool.set_milestones(builder, &[milestone(3_000), milestone(7_000)])
+            .unwrap();
+        assert_eq!(pool.builder, builder);
+        assert_eq!(pool.milestones().len(), 2);
+
+        // Once contributions open, neither the builder nor the plan can change
+        pool.transition(ProjectStatus::Funding).unwrap();
+        let err = pool
+            .set_milestones(Pubkey::new_unique(), &[milestone(10_000)])
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::MilestonesLocked.into());
+        assert_eq!(pool.builder, builder);
+        pool.transition(ProjectStatus::Funded).unwrap();
+        let err = pool.set_milestones(builder, &[milestone(10_000)]).unwrap_err();
+        assert_eq!(err, ErrorCode::MilestonesLocked.into());
     }
 
     #[test]
-    fn poc_receipt_validation_rejects_invalid_inputs() {
-        let mut receipt = ProofOfCollectionReceipt {
-            player: Pubkey::default(),
+    fn project_pool_releases_milestones_in_order() {
+        let mut pool = test_pool();
+        pool.set_milestones(
+            Pubkey::new_unique(),
+            &[milestone(2_500), milestone(2_500), milestone(5_000)],
+        )
+        .unwrap();
+
+        pool.transition(ProjectStatus::Funding).unwrap();
+        let err = pool.release_next_milestone(&[11, 8]).unwrap_err();
+        assert_eq!(err, ErrorCode::ProjectNotFunded.into());
+        pool.transition(ProjectStatus::Funded).unwrap();
+        let err = pool.release_next_milestone(&[11]).unwrap_err();
+        assert_eq!(err, ErrorCode::MaterialVaultMissing.into());
+
+        // Each vault pays its own tranche: 25% of 11 rounds down, and the
+        // last milestone sweeps the remainder
+        let paid = |releases: &[MaterialRelease]| -> Vec<(u64, u64)> {
+            releases.iter().map(|r| (r.amount, r.remaining)).collect()
+        };
+        let (index, releases) = pool.release_next_milestone(&[11, 8]).unwrap();
+        assert_eq!(index, 0);
+        assert_eq!(releases[1].material_id, 1);
+        assert_eq!(releases[1].mint, pool.material_goals()[1].mint);
+        assert_eq!(paid(&releases), vec![(2, 9), (2, 6)]);
+        assert_eq!(pool.received_credits, 15);
+        let (index, releases) = pool.release_next_milestone(&[9, 6]).unwrap();
+        assert_eq!((index, paid(&releases)), (1, vec![(2, 7), (2, 4)]));
+        let (index, releases) = pool.release_next_milestone(&[7, 4]).unwrap();
+        assert_eq!((index, paid(&releases)), (2, vec![(7, 0), (4, 0)]));
+        assert_eq!(pool.released_credits, 19);
+        assert_eq!(pool.material_goals()[0].released, 11);
+        assert_eq!(pool.received_credits, 0);
+
+        let err = pool.release_next_milestone(&[0, 0]).unwrap_err();
+        assert_eq!(err, ErrorCode::NoMilestoneToRelease.into());
+        let err = pool.transition(ProjectStatus::Cancelled).unwrap_err();
+        assert_eq!(err, ErrorCode::ProjectNotCancellable.into());
+    }
+
+    #[test]
+    fn construction_receipt_attestation_message_is_canonical() {
+        let pool = Pubkey::new_unique();
+        let mut receipt = ConstructionReceipt {
+            project_pool: Pubkey::default(),
+            milestone_index: 0,
             bump: 0,
-            attestation_id: [0u8; 32],
+            description_hash: [0u8; 32],
             photo_hash: [0u8; 32],
             zone_id_len: 0,
             zone_id: [0u8; ProofOfCollectionReceipt::ZONE_ID_MAX_LEN],
-            material_type: 0,
-            quantity: 0,
             timestamp: 0,
+            verifier: Pubkey::default(),
+            signature: [0u8; 64],
         };
-
-        let too_long_zone = "z".repeat(ProofOfCollectionReceipt::ZONE_ID_MAX_LEN + 1);
         let err = receipt
-            .initialize(
-                Pubkey::new_unique(),
-                1,
-                [1u8; 32],
-                [2u8; 32],
-                &too_long_zone,
-                MaterialType::Plastic as u8,
-                1,
-                1,
-            )
+            .initialize(pool, 1, 255, [9u8; 32], [5u8; 32], "site-a", 0)
             .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidTimestamp.into());
 
-        assert_eq!(err, ErrorCode::ZoneIdTooLong.into());
+        receipt
+            .initialize(pool, 1, 255, [9u8; 32], [5u8; 32], "site-a", 1_700_000_000)
+            .unwrap();
+        let message = receipt.attestation_message();
+        let domain_len = ConstructionReceipt::ATTESTATION_DOMAIN.len();
+        assert_eq!(&message[..domain_len], ConstructionReceipt::ATTESTATION_DOMAIN);
+        assert_eq!(&message[domain_len..domain_len + 32], pool.as_ref());
+        assert_eq!(message[domain_len + 32], 1);
+        assert_eq!(&message[domain_len + 33..domain_len + 65], &[9u8; 32]);
+        assert_eq!(&message[domain_len + 65..domain_len + 97], &[5u8; 32]);
+        assert_eq!(message[domain_len + 97], 6);
+        assert_eq!(&message[domain_len + 98..domain_len + 104], b"site-a");
+        assert_eq!(message.len(), domain_len + 112);
+        assert_eq!(
+            ConstructionReceipt::SIZE,
+            8 + 32 + 1 + 1 + 32 + 32 + 1 + 32 + 8 + 32 + 64
+        );
 
-        let err = receipt
-            .initialize(
-                Pubkey::new_unique(),
-                1,
-                [1u8; 32],
-                [2u8; 32],
-                "zone-1",
-                9,
-                1,
-                1,
-            )
-            .unwrap_err();
-        assert_eq!(err, ErrorCode::InvalidMaterialType.into());
+        // A signature over one milestone description never covers another
+        receipt.description_hash = [8u8; 32];
+        assert_ne!(receipt.attestation_message(), message);
+    }
 
-        let err = receipt
-            .initialize(
-                Pubkey::new_unique(),
-                1,
-                [1u8; 32],
-                [2u8; 32],
-                "zone-1",
-                MaterialType::Plastic as u8,
-                0,
-                1,
-            )
-            .unwrap_err();
+    fn test_contribution() -> Contribution {
+        Contribution {
+            project_pool: Pubkey::default(),
+            contributor: Pubkey::default(),
+            bump: 0,
+            amount: 0,
+            amounts: [0u64; ProjectPool::MAX_MATERIAL_GOALS],
+            refunded: false,
+            first_contributed_at: 0,
+            last_contributed_at: 0,
+            count: 0,
+            badge_claimed: false,
+        }
+    }
+
+    #[test]
+    fn contribution_refunds_once() {
+        let mut contribution = test_contribution();
+        contribution.initialize(Pubkey::new_unique(), Pubkey::new_unique(), 3);
+        let err = contribution.refund(0).unwrap_err();
+        assert_eq!(err, ErrorCode::NothingToRefund.into());
+
+        contribution.record(0, 5, 100).unwrap();
+        contribution.record(1, 2, 250).unwrap();
+        contribution.record(0, 1, 300).unwrap();
+        assert_eq!(contribution.count, 3);
+        assert_eq!(contribution.amount, 8);
+        assert_eq!(contribution.first_contributed_at, 100);
+        assert_eq!(contribution.last_contributed_at, 300);
+
+        // Each material is refunded from its own vault, once
+        assert_eq!(contribution.refund(0).unwrap(), 6);
+        assert!(!contribution.refunded);
+        let err = contribution.refund(0).unwrap_err();
+        assert_eq!(err, ErrorCode::NothingToRefund.into());
+        assert_eq!(contribution.refund(1).unwrap(), 2);
+        assert!(contribution.refunded);
+    }
+
+    #[test]
+    fn contribution_size_is_correct() {
+        assert_eq!(
+            Contribution::SIZE,
+            8 + 32 + 32 + 1 + 8 + ProjectPool::MAX_MATERIAL_GOALS * 8 + 1 + 8 + 8 + 4 + 1
+        );
+    }
+
+    #[test]
+    fn project_ids_are_sequential_and_authority_transfers() {
+        let mut config = test_config(Pubkey::new_unique());
+        assert_eq!(config.next_project_id().unwrap(), 0);
+        assert_eq!(config.next_project_id().unwrap(), 1);
+        assert_eq!(config.project_count, 2);
+
+        let mut pool = test_pool();
+        let curator = pool.authority;
+        let org = Pubkey::new_unique();
+        let err = pool.propose_authority(curator).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidProjectAuthority.into());
+        let err = pool.accept_authority(org).unwrap_err();
+        assert_eq!(err, ErrorCode::Unauthorized.into());
+
+        // Only the proposed key can take over, and only by accepting
+        pool.propose_authority(org).unwrap();
+        assert_eq!(pool.authority, curator);
+        let err = pool.accept_authority(Pubkey::new_unique()).unwrap_err();
+        assert_eq!(err, ErrorCode::Unauthorized.into());
+        assert_eq!(pool.accept_authority(org).unwrap(), curator);
+        assert_eq!(pool.authority, org);
+        assert_eq!(pool.pending_authority, Pubkey::default());
+
+        // A withdrawn proposal cannot be accepted
+        pool.propose_authority(curator).unwrap();
+        pool.propose_authority(Pubkey::default()).unwrap();
+        let err = pool.accept_authority(curator).unwrap_err();
+        assert_eq!(err, ErrorCode::Unauthorized.into());
+    }
+
+    fn test_project_metadata() -> ProjectMetadata {
+        ProjectMetadata {
+            project_pool: Pubkey::default(),
+            bump: 0,
+            uri_len: 0,
+            uri: [0u8; ProjectMetadata::URI_MAX_LEN],
+            content_hash: [0u8; 32],
+            image_uri_len: 0,
+            image_uri: [0u8; ProjectMetadata::URI_MAX_LEN],
+            category: ProjectCategory::Other,
+            zone_id_len: 0,
+            zone_id: [0u8; ProofOfCollectionReceipt::ZONE_ID_MAX_LEN],
+            version: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn project_metadata_updates_while_open() {
+        let mut pool = test_pool();
+        assert!(pool.is_open());
+        pool.transition(ProjectStatus::Funding).unwrap();
+        assert!(pool.is_open());
+        pool.transition(ProjectStatus::Funded).unwrap();
+        assert!(!pool.is_open());
+
+        let mut metadata = test_project_metadata();
+        let mut params = ProjectMetadataParams {
+            uri: "ipfs://project".to_string(),
+            content_hash: [4u8; 32],
+            image_uri: "ipfs://image".to_string(),
+            category: ProjectCategory::School,
+            zone_id: "zone-7".to_string(),
+        };
+        metadata.update(&params, 100).unwrap();
+        assert_eq!(metadata.uri(), "ipfs://project");
+        assert_eq!(metadata.image_uri(), "ipfs://image");
+        assert_eq!(metadata.zone_id(), "zone-7");
+        assert_eq!(metadata.category, ProjectCategory::School);
+        assert_eq!(metadata.version, 1);
+
+        params.uri = "ipfs://v2".to_string();
+        metadata.update(&params, 200).unwrap();
+        assert_eq!(metadata.uri(), "ipfs://v2");
+        assert_eq!(metadata.version, 2);
+        assert_eq!(metadata.updated_at, 200);
+
+        params.image_uri = "x".repeat(ProjectMetadata::URI_MAX_LEN + 1);
+        let err = metadata.update(&params, 300).unwrap_err();
+        assert_eq!(err, ErrorCode::ProjectUriTooLong.into());
+        assert_eq!(metadata.version, 2);
+    }
+
+    #[test]
+    fn completion_badge_claims_once_with_share() {
+        let mut pool = test_pool();
+        fund(&mut pool, 0, 6);
+        pool.released_credits = 4;
+        assert_eq!(pool.total_raised().unwrap(), 10);
+        assert_eq!(pool.contribution_share_bps(3).unwrap(), 3_000);
+        let err = pool.contribution_share_bps(11).unwrap_err();
         assert_eq!(err, ErrorCode::InvalidAmount.into());
+
+        let mut contribution = test_contribution();
+        let err = contribution.claim_badge().unwrap_err();
+        assert_eq!(err, ErrorCode::NotAContributor.into());
+
+        contribution.record(0, 3, 100).unwrap();
+        contribution.claim_badge().unwrap();
+        assert!(contribution.badge_claimed);
+        let err = contribution.claim_badge().unwrap_err();
+        assert_eq!(err, ErrorCode::BadgeAlreadyClaimed.into());
+        assert_eq!(CompletionBadge::name(&pool.name()), "EcoBuild: Bench");
     }
 
     #[test]
-    fn global_config_size_is_correct() {
-        assert_eq!(GlobalConfig::SIZE, 8 + 32 + 32 + 8 + 8 + 1);
+    fn completion_badge_uri_falls_back_to_pool_name() {
+        assert_eq!(
+            CompletionBadge::uri(None, "Park Bench #2"),
+            "ecobuild://projects/park-bench-2"
+        );
+
+        let mut metadata = test_project_metadata();
+        assert_eq!(
+            CompletionBadge::uri(Some(&metadata), "Bench"),
+            "ecobuild://projects/bench"
+        );
+        let params = ProjectMetadataParams {
+            uri: "ipfs://bench".to_string(),
+            content_hash: [0u8; 32],
+            image_uri: String::new(),
+            category: ProjectCategory::Other,
+            zone_id: String::new(),
+        };
+        metadata.update(&params, 100).unwrap();
+        assert_eq!(CompletionBadge::uri(Some(&metadata), "Bench"), "ipfs://bench");
     }
 
     #[test]
-    fn player_profile_size_includes_new_fields() {
-        assert_eq!(PlayerProfile::SIZE, 8 + 32 + 1 + 8 + 8 + 8 + 8);
+    fn completion_badge_metadata_carries_project_and_share() {
+        let project = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let metadata = CompletionBadge::metadata(
+            project,
+            mint,
+            "Bench",
+            "ipfs://bench".to_string(),
+            3,
+            3_000,
+        )
+        .unwrap();
+        assert_eq!(
+            Option::<Pubkey>::from(metadata.update_authority),
+            Some(project)
+        );
+        assert_eq!(metadata.mint, mint);
+        assert_eq!(metadata.name, "EcoBuild: Bench");
+        assert_eq!(metadata.symbol, CompletionBadge::SYMBOL);
+        assert_eq!(metadata.uri, "ipfs://bench");
+        assert_eq!(
+            metadata.additional_metadata,
+            vec![
+                ("project".to_string(), project.to_string()),
+                ("contributed".to_string(), "3".to_string()),
+                ("share_bps".to_string(), "3000".to_string()),
+            ]
+        );
     }
 
     #[test]
-    fn material_type_try_from_valid() {
-        assert_eq!(MaterialType::try_from(0).unwrap(), MaterialType::Plastic);
-        assert_eq!(MaterialType::try_from(1).unwrap(), MaterialType::Glass);
-        assert_eq!(MaterialType::try_from(2).unwrap(), MaterialType::Metal);
-        assert_eq!(MaterialType::try_from(3).unwrap(), MaterialType::Paper);
+    fn material_config_size_is_correct() {
+        assert_eq!(MaterialConfig::SIZE, 8 + 1 + 1 + 1 + 8 + 33 + 1 + 32);
     }
 
     #[test]
-    fn material_type_try_from_invalid() {
-        let err = MaterialType::try_from(4).unwrap_err();
-        assert_eq!(err, ErrorCode::InvalidMaterialType.into());
+    fn global_config_bounds_receipt_timestamps() {
+        let mut config = test_config(Pubkey::new_unique());
+        let now = 1_700_000_000;
+        config.check_receipt_timestamp(now, now).unwrap();
+        config
+            .check_receipt_timestamp(now + DEFAULT_MAX_FUTURE_SKEW, now)
+            .unwrap();
+        config
+            .check_receipt_timestamp(now - DEFAULT_MAX_RECEIPT_AGE, now)
+            .unwrap();
+
+        let err = config
+            .check_receipt_timestamp(now + DEFAULT_MAX_FUTURE_SKEW + 1, now)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::ReceiptTimestampInFuture.into());
+        let err = config
+            .check_receipt_timestamp(now - DEFAULT_MAX_RECEIPT_AGE - 1, now)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::ReceiptTimestampStale.into());
+
+        config
+            .apply_params(&ConfigParams {
+                max_receipt_age: Some(60),
+                max_future_skew: Some(0),
+                ..Default::default()
+            })
+            .unwrap();
+        let err = config.check_receipt_timestamp(now + 1, now).unwrap_err();
+        assert_eq!(err, ErrorCode::ReceiptTimestampInFuture.into());
+        let err = config.check_receipt_timestamp(now - 61, now).unwrap_err();
+        assert_eq!(err, ErrorCode::ReceiptTimestampStale.into());
+
+        let err = config
+            .apply_params(&ConfigParams {
+                max_receipt_age: Some(0),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidConfigParam.into());
+    }
+
+    #[test]
+    fn default_blocks_per_brick_constant() {
+        assert_eq!(DEFAULT_BLOCKS_PER_BRICK, 10);
+    }
+
+    #[test]
+    fn global_config_blocks_per_brick_is_adjustable() {
+        let mut config = test_config(Pubkey::new_unique());
+        config
+            .apply_params(&ConfigParams {
+                blocks_per_brick: Some(25),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(config.blocks_per_brick, 25);
+
+        let err = config
+            .apply_params(&ConfigParams {
+                blocks_per_brick: Some(0),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidConfigParam.into());
+        assert_eq!(config.blocks_per_brick, 25);
     }
 
     #[test]
-    fn blocks_per_brick_constant() {
-        assert_eq!(BLOCKS_PER_BRICK, 10);
+    fn global_config_blocks_for_bricks_checks_math() {
+        let config = test_config(Pubkey::new_unique());
+        assert_eq!(config.blocks_for_bricks(1).unwrap(), DEFAULT_BLOCKS_PER_BRICK);
+        assert_eq!(config.blocks_for_bricks(50).unwrap(), 500);
+
+        let err = config.blocks_for_bricks(0).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidAmount.into());
+        let err = config.blocks_for_bricks(u64::MAX).unwrap_err();
+        assert_eq!(err, ErrorCode::Overflow.into());
     }
 }
@@ -52,4 +52,5 @@ anchor deploy --provider.cluster devnet
 
 echo
 echo "[deploy-devnet] Deploy complete."
-echo "  To run the demo against devnet:  SIM_MODE=0 scripts/demo.sh"
+echo "  To run the demo against devnet:"
+echo "    SOLANA_URL=https://api.devnet.solana.com DEMO_IMAGE=<photo> SIM_MODE=0 scripts/demo.sh"
@@ -1,5 +1,6 @@
 import * as anchor from "@coral-xyz/anchor";
 import { BN } from "bn.js";
+import crypto from "crypto";
 import { expect } from "chai";
 import {
   getAssociatedTokenAddress,
@@ -14,6 +15,8 @@ describe("ecobuild", () => {
 
   const program = anchor.workspace.Ecobuild;
   const authority = provider.wallet.publicKey;
+  // The test wallet is also the only registered verifier
+  const verifierKeypair = (provider.wallet as anchor.Wallet).payer;
 
   // PDAs
   const [playerPda] = anchor.web3.PublicKey.findProgramAddressSync(
@@ -28,11 +31,137 @@ describe("ecobuild", () => {
     [Buffer.from("block_mint")],
     program.programId
   );
+  const [brickMintPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("brick_mint")],
+    program.programId
+  );
+
+  const [verifierRegistryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("verifier_registry")],
+    program.programId
+  );
+
+  const zoneId = "zone-17";
+  const [zonePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("zone"), Buffer.from(zoneId)],
+    program.programId
+  );
+  const location = { latE7: 377_749_000, lonE7: -1_224_194_000 };
+
+  const materialPda = (materialId: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("material"), Buffer.from([materialId])],
+      program.programId
+    )[0];
+
+  const sha256 = (data: Buffer) =>
+    crypto.createHash("sha256").update(data).digest();
+
+  // Same layout as ProofOfCollectionReceipt::attestation_message
+  const attestationHash = (
+    attestationId: Buffer,
+    photoHash: Buffer,
+    materialType: number,
+    quantity: number,
+    timestamp: number
+  ) => {
+    const zone = Buffer.from(zoneId);
+    const coords = Buffer.alloc(8);
+    coords.writeInt32LE(location.latE7, 0);
+    coords.writeInt32LE(location.lonE7, 4);
+    const ts = Buffer.alloc(8);
+    ts.writeBigInt64LE(BigInt(timestamp));
+    return sha256(
+      Buffer.concat([
+        Buffer.from("ecobuild:poc:v2"),
+        authority.toBuffer(),
+        attestationId,
+        photoHash,
+        Buffer.from([zone.length]),
+        zone,
+        coords,
+        Buffer.from([materialType]),
+        new BN(quantity).toArrayLike(Buffer, "le", 8),
+        ts,
+      ])
+    );
+  };
+
+  // Submits a verifier-signed receipt and redeems it for BLOCK
+  const collect = async (materialType: number, quantity: number) => {
+    const attestationId = crypto.randomBytes(32);
+    const photoHash = crypto.randomBytes(32);
+    const timestamp = Math.floor(Date.now() / 1000);
+    const [receiptPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("poc"), authority.toBuffer(), attestationId],
+      program.programId
+    );
+    const [photoClaimPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("photo"), photoHash],
+      program.programId
+    );
+
+    const signature = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: verifierKeypair.secretKey,
+      message: attestationHash(
+        attestationId,
+        photoHash,
+        materialType,
+        quantity,
+        timestamp
+      ),
+    });
+
+    await program.methods
+      .mintPocReceipt(
+        Array.from(attestationId),
+        Array.from(photoHash),
+        zoneId,
+        materialType,
+        new BN(quantity),
+        new BN(timestamp),
+        location
+      )
+      .accounts({
+        authority,
+        playerProfile: playerPda,
+        globalConfig: globalConfigPda,
+        materialConfig: materialPda(materialType),
+        zone: zonePda,
+        pocReceipt: receiptPda,
+        photoHashClaim: photoClaimPda,
+        verifierRegistry: verifierRegistryPda,
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .preInstructions([signature])
+      .rpc();
+
+    return program.methods
+      .mintBlocks()
+      .accounts({
+        authority,
+        globalConfig: globalConfigPda,
+        blockMint: blockMintPda,
+        playerProfile: playerPda,
+        pocReceipt: receiptPda,
+        materialConfig: materialPda(materialType),
+        zone: zonePda,
+        playerTokenAccount: playerAta,
+        playerAuthority: authority,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  };
 
   let playerAta: anchor.web3.PublicKey;
+  let brickAta: anchor.web3.PublicKey;
 
   before(async () => {
     playerAta = await getAssociatedTokenAddress(blockMintPda, authority);
+    brickAta = await getAssociatedTokenAddress(brickMintPda, authority);
   });
 
   it("initializes a player profile PDA", async () => {
@@ -47,20 +176,22 @@ describe("ecobuild", () => {
     const account = await program.account.playerProfile.fetch(playerPda);
     expect(account.authority.toBase58()).to.equal(authority.toBase58());
     expect(account.bump).to.be.a("number");
-    expect(account.totalCredits.toNumber()).to.equal(0);
+    expect(account.creditsContributed.toNumber()).to.equal(0);
     expect(account.blocksMinted.toNumber()).to.equal(0);
     expect(account.brickCount.toNumber()).to.equal(0);
     expect(account.collectionsCount.toNumber()).to.equal(0);
     expect(tx).to.be.a("string");
   });
 
-  it("initializes global config and creates BLOCK mint", async () => {
+  it("initializes global config and creates BLOCK and BRICK mints", async () => {
+    // The test wallet doubles as the verifier
     const tx = await program.methods
-      .initializeConfig()
+      .initializeConfig(authority)
       .accounts({
         authority,
         globalConfig: globalConfigPda,
         blockMint: blockMintPda,
+        brickMint: brickMintPda,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -69,31 +200,85 @@ describe("ecobuild", () => {
 
     const config = await program.account.globalConfig.fetch(globalConfigPda);
     expect(config.authority.toBase58()).to.equal(authority.toBase58());
+    expect(config.verifier.toBase58()).to.equal(authority.toBase58());
     expect(config.blockMint.toBase58()).to.equal(blockMintPda.toBase58());
+    expect(config.brickMint.toBase58()).to.equal(brickMintPda.toBase58());
     expect(config.totalBlocksMinted.toNumber()).to.equal(0);
     expect(config.totalBricksCreated.toNumber()).to.equal(0);
     expect(tx).to.be.a("string");
   });
 
-  it("mints BLOCK tokens to player", async () => {
-    const mintAmount = 25;
-    const wasteType = 0; // Plastic
+  it("registers the verifier, materials and a collection zone", async () => {
+    // No challenge window or bond, so receipts can be redeemed right away
+    await program.methods
+      .updateConfig({
+        verifier: null,
+        curator: null,
+        pauser: null,
+        arbiter: null,
+        blocksPerBrick: null,
+        challengePeriod: new BN(0),
+        disputeBond: new BN(0),
+        maxReceiptAge: null,
+        maxFutureSkew: null,
+      })
+      .accounts({ authority, globalConfig: globalConfigPda })
+      .rpc();
 
-    const tx = await program.methods
-      .mintBlocks(new BN(mintAmount), wasteType)
+    await program.methods
+      .initializeVerifierRegistry([authority], 1)
       .accounts({
         authority,
         globalConfig: globalConfigPda,
-        blockMint: blockMintPda,
-        playerProfile: playerPda,
-        playerTokenAccount: playerAta,
-        playerAuthority: authority,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        verifierRegistry: verifierRegistryPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    for (const [materialId, name] of [
+      [0, "plastic"],
+      [1, "glass"],
+    ] as const) {
+      await program.methods
+        .createMaterial(materialId, name, new BN(1), null)
+        .accounts({
+          authority,
+          globalConfig: globalConfigPda,
+          materialConfig: materialPda(materialId),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    await program.methods
+      .createZone(zoneId, "Test zone", {
+        minLatE7: 370_000_000,
+        minLonE7: -1_230_000_000,
+        maxLatE7: 380_000_000,
+        maxLonE7: -1_220_000_000,
+      })
+      .accounts({
+        authority,
+        globalConfig: globalConfigPda,
+        zone: zonePda,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
+    const registry = await program.account.verifierRegistry.fetch(
+      verifierRegistryPda
+    );
+    expect(registry.threshold).to.equal(1);
+    const zone = await program.account.zone.fetch(zonePda);
+    expect(zone.active).to.equal(true);
+  });
+
+  it("mints BLOCK tokens for a verified receipt", async () => {
+    const mintAmount = 25;
+    const wasteType = 0; // Plastic
+
+    const tx = await collect(wasteType, mintAmount);
+
     // Check token balance
     const tokenAccount = await getAccount(provider.connection, playerAta);
     expect(Number(tokenAccount.amount)).to.equal(mintAmount);
@@ -113,20 +298,7 @@ describe("ecobuild", () => {
     const mintAmount = 15;
     const wasteType = 1; // Glass
 
-    await program.methods
-      .mintBlocks(new BN(mintAmount), wasteType)
-      .accounts({
-        authority,
-        globalConfig: globalConfigPda,
-        blockMint: blockMintPda,
-        playerProfile: playerPda,
-        playerTokenAccount: playerAta,
-        playerAuthority: authority,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      })
-      .rpc();
+    await collect(wasteType, mintAmount);
 
     // 25 + 15 = 40
     const tokenAccount = await getAccount(provider.connection, playerAta);
@@ -138,24 +310,36 @@ describe("ecobuild", () => {
 
     const config = await program.account.globalConfig.fetch(globalConfigPda);
     expect(config.totalBlocksMinted.toNumber()).to.equal(40);
+
+    // Only minted receipts count toward the zone's totals
+    const zone = await program.account.zone.fetch(zonePda);
+    expect(zone.receiptsCount.toNumber()).to.equal(2);
+    expect(zone.materialTotals[0].toNumber()).to.equal(25);
+    expect(zone.materialTotals[1].toNumber()).to.equal(15);
   });
 
   it("converts 10 BLOCK tokens to 1 Brick", async () => {
     const tx = await program.methods
-      .convertToBrick()
+      .convertToBrick(new BN(1))
       .accounts({
         authority,
         globalConfig: globalConfigPda,
         blockMint: blockMintPda,
+        brickMint: brickMintPda,
         playerProfile: playerPda,
         playerTokenAccount: playerAta,
+        playerBrickAccount: brickAta,
         tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
     // 40 - 10 = 30
     const tokenAccount = await getAccount(provider.connection, playerAta);
     expect(Number(tokenAccount.amount)).to.equal(30);
+    const brickAccount = await getAccount(provider.connection, brickAta);
+    expect(Number(brickAccount.amount)).to.equal(1);
 
     const player = await program.account.playerProfile.fetch(playerPda);
     expect(player.brickCount.toNumber()).to.equal(1);
@@ -167,20 +351,26 @@ describe("ecobuild", () => {
 
   it("converts another 10 BLOCK to Brick (cumulative)", async () => {
     await program.methods
-      .convertToBrick()
+      .convertToBrick(new BN(1))
       .accounts({
         authority,
         globalConfig: globalConfigPda,
         blockMint: blockMintPda,
+        brickMint: brickMintPda,
         playerProfile: playerPda,
         playerTokenAccount: playerAta,
+        playerBrickAccount: brickAta,
         tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
 
     // 30 - 10 = 20
     const tokenAccount = await getAccount(provider.connection, playerAta);
     expect(Number(tokenAccount.amount)).to.equal(20);
+    const brickAccount = await getAccount(provider.connection, brickAta);
+    expect(Number(brickAccount.amount)).to.equal(2);
 
     const player = await program.account.playerProfile.fetch(playerPda);
     expect(player.brickCount.toNumber()).to.equal(2);
@@ -189,48 +379,51 @@ describe("ecobuild", () => {
     expect(config.totalBricksCreated.toNumber()).to.equal(2);
   });
 
-  it("fails to convert when insufficient BLOCK tokens", async () => {
-    // Burn down to 5 tokens: convert twice more (20 -> 10 -> 0), then we'd need more
-    // Actually player has 20 tokens. Convert twice to get to 0, then try again.
+  it("converts several bricks at once", async () => {
     await program.methods
-      .convertToBrick()
-      .accounts({
-        authority,
-        globalConfig: globalConfigPda,
-        blockMint: blockMintPda,
-        playerProfile: playerPda,
-        playerTokenAccount: playerAta,
-        tokenProgram: TOKEN_PROGRAM_ID,
-      })
-      .rpc(); // 20 -> 10
-
-    await program.methods
-      .convertToBrick()
+      .convertToBrick(new BN(2))
       .accounts({
         authority,
         globalConfig: globalConfigPda,
         blockMint: blockMintPda,
+        brickMint: brickMintPda,
         playerProfile: playerPda,
         playerTokenAccount: playerAta,
+        playerBrickAccount: brickAta,
         tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
-      .rpc(); // 10 -> 0
+      .rpc(); // 20 -> 0
 
-    // Verify balance is 0
     const tokenAccount = await getAccount(provider.connection, playerAta);
     expect(Number(tokenAccount.amount)).to.equal(0);
+    const brickAccount = await getAccount(provider.connection, brickAta);
+    expect(Number(brickAccount.amount)).to.equal(4);
 
-    // Now try to convert with 0 balance — should fail
+    const player = await program.account.playerProfile.fetch(playerPda);
+    expect(player.brickCount.toNumber()).to.equal(4);
+
+    const config = await program.account.globalConfig.fetch(globalConfigPda);
+    expect(config.totalBricksCreated.toNumber()).to.equal(4);
+  });
+
+  it("fails to convert when insufficient BLOCK tokens", async () => {
+    // The balance is 0 now, so even a single brick should fail
     try {
       await program.methods
-        .convertToBrick()
+        .convertToBrick(new BN(1))
         .accounts({
           authority,
           globalConfig: globalConfigPda,
           blockMint: blockMintPda,
+          brickMint: brickMintPda,
           playerProfile: playerPda,
           playerTokenAccount: playerAta,
+          playerBrickAccount: brickAta,
           tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc();
       expect.fail("should have thrown InsufficientBlocks error");